@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, io};
 
 use crate::{checker::structs::CheckerType, parser::{error::ConfigError, keys::ConfigKeyInvalidFormat, ConfigKey}};
 
@@ -9,6 +9,7 @@ pub enum CheckerParseError {
     InvalidType(String),
     InvalidFormat(ConfigKey, ConfigKeyInvalidFormat),
     InternalParse(String),
+    UnableToReadFile(String, io::Error),
 }
 impl Display for CheckerParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -22,6 +23,10 @@ impl Display for CheckerParseError {
                 write!(f, "Invalid format for '{key}'. Expected: {format}")
             }
             CheckerParseError::InternalParse(e) => write!(f, "{e}"),
+            CheckerParseError::UnableToReadFile(path, e) => write!(
+                f,
+                "Unable to read the desired file.\nPath: {path}\nError: {e}"
+            ),
         }
     }
 }
@@ -0,0 +1,343 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde_json::Value;
+
+/// A step of a JSON path, a key of an object or an index of an array.
+#[derive(Debug)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// A JSONPath-like selector, like `$.checks[0].status` or `$["queue-depth"]`.
+#[derive(Debug)]
+pub struct JsonPath {
+    raw: String,
+    segments: Vec<Segment>,
+}
+impl JsonPath {
+    fn select<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(value, |value, segment| match segment {
+                Segment::Key(key) => value.get(key),
+                Segment::Index(index) => value.get(index),
+            })
+    }
+}
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+impl FromStr for JsonPath {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(mut rest) = s.strip_prefix('$') else {
+            return Err(format!("The path '{s}' must start with '$'"));
+        };
+
+        let mut segments = vec![];
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+                if end == 0 {
+                    return Err(format!("The path '{s}' has an empty key"));
+                }
+                segments.push(Segment::Key(after_dot[..end].to_string()));
+                rest = &after_dot[end..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let Some(end) = after_bracket.find(']') else {
+                    return Err(format!("The path '{s}' has an unclosed '['"));
+                };
+                let inner = &after_bracket[..end];
+                let segment = match inner.parse::<usize>() {
+                    Ok(index) => Segment::Index(index),
+                    Err(_) => match inner
+                        .strip_prefix('"')
+                        .and_then(|key| key.strip_suffix('"'))
+                        .or_else(|| inner.strip_prefix('\'')?.strip_suffix('\''))
+                    {
+                        Some(key) => Segment::Key(key.to_string()),
+                        None => {
+                            return Err(format!(
+                                "The path '{s}' must use an index or a quoted key inside '[]'"
+                            ));
+                        }
+                    },
+                };
+                segments.push(segment);
+                rest = &after_bracket[end + 1..];
+            } else {
+                return Err(format!("The path '{s}' must use '.' or '[]' after '$'"));
+            }
+        }
+
+        Ok(Self {
+            raw: s.to_string(),
+            segments,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Lower,
+    LowerOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+impl Comparison {
+    /// The operators of the comparisons, the two characters ones are matched first.
+    const OPERATORS: [(&str, Comparison); 6] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LowerOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Lower),
+        (">", Comparison::Greater),
+    ];
+
+    /// Whether the comparison orders numbers, so it only accepts a number as expected value.
+    fn is_ordering(&self) -> bool {
+        !matches!(self, Comparison::Equal | Comparison::NotEqual)
+    }
+
+    fn is_satisfied(&self, actual: &Value, expected: &Value) -> bool {
+        let numbers = actual.as_f64().zip(expected.as_f64());
+        match self {
+            Comparison::Equal => numbers.map_or(actual == expected, |(a, e)| a == e),
+            Comparison::NotEqual => numbers.map_or(actual != expected, |(a, e)| a != e),
+            Comparison::Lower => numbers.is_some_and(|(a, e)| a < e),
+            Comparison::LowerOrEqual => numbers.is_some_and(|(a, e)| a <= e),
+            Comparison::Greater => numbers.is_some_and(|(a, e)| a > e),
+            Comparison::GreaterOrEqual => numbers.is_some_and(|(a, e)| a >= e),
+        }
+    }
+}
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::Equal => write!(f, "=="),
+            Comparison::NotEqual => write!(f, "!="),
+            Comparison::Lower => write!(f, "<"),
+            Comparison::LowerOrEqual => write!(f, "<="),
+            Comparison::Greater => write!(f, ">"),
+            Comparison::GreaterOrEqual => write!(f, ">="),
+        }
+    }
+}
+
+/// An assertion on a JSON response body, written like `$.status == "ok"`,
+/// `$.queue_depth < 1000` or `$.version exists`.
+/// The expected value is read as JSON, a value which isn't is taken as a string,
+/// while `<`, `<=`, `>` and `>=` only accept a number.
+#[derive(Debug)]
+pub enum JsonAssertion {
+    Exists(JsonPath),
+    Compare(JsonPath, Comparison, Value),
+}
+impl JsonAssertion {
+    /// Describe why the `body` doesn't satisfy the assertion, naming the path and its value.
+    pub fn failure(&self, body: &Value) -> Option<String> {
+        match self {
+            JsonAssertion::Exists(path) => path
+                .select(body)
+                .is_none()
+                .then(|| format!("{path} does not exist")),
+            JsonAssertion::Compare(path, comparison, expected) => match path.select(body) {
+                None => Some(format!(
+                    "{path} does not exist, expected {comparison} {expected}"
+                )),
+                Some(actual) if !comparison.is_satisfied(actual, expected) => Some(format!(
+                    "{path} is {actual}, expected {comparison} {expected}"
+                )),
+                Some(_) => None,
+            },
+        }
+    }
+}
+impl FromStr for JsonAssertion {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // The path ends at the first whitespace out of its brackets, as a key inside may have some
+        let mut in_brackets = false;
+        let path_end = s
+            .char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '[' => in_brackets = true,
+                    ']' => in_brackets = false,
+                    _ => {}
+                }
+                !in_brackets && c.is_whitespace()
+            })
+            .map_or(s.len(), |(index, _)| index);
+        let (path, rest) = s.split_at(path_end);
+        let path = JsonPath::from_str(path)?;
+        let rest = rest.trim_start();
+        if rest == "exists" {
+            return Ok(JsonAssertion::Exists(path));
+        }
+
+        let (comparison, expected) = Comparison::OPERATORS
+            .into_iter()
+            .find_map(|(operator, comparison)| {
+                rest.strip_prefix(operator)
+                    .map(|expected| (comparison, expected.trim()))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "The assertion '{s}' must use ==, !=, <, <=, >, >= or exists after the path"
+                )
+            })?;
+        if expected.is_empty() {
+            return Err(format!("The assertion '{s}' has no expected value"));
+        }
+        if expected.starts_with(['=', '<', '>', '!']) {
+            return Err(format!("The assertion '{s}' has an unknown operator"));
+        }
+
+        let expected =
+            serde_json::from_str(expected).unwrap_or_else(|_| Value::String(expected.to_string()));
+        if comparison.is_ordering() && !expected.is_number() {
+            return Err(format!(
+                "The assertion '{s}' must compare with a number when using {comparison}"
+            ));
+        }
+        Ok(JsonAssertion::Compare(path, comparison, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn path_from_str() {
+        let body = json!({
+            "status": "ok",
+            "checks": [{"status": "up"}, {"status": "down"}],
+            "queue-depth": 12,
+            "nested": {"a.b": true},
+        });
+        let cases = [
+            ("$", Some(&body)),
+            ("$.status", Some(&body["status"])),
+            ("$.checks[1].status", Some(&body["checks"][1]["status"])),
+            ("$[\"queue-depth\"]", Some(&body["queue-depth"])),
+            ("$['queue-depth']", Some(&body["queue-depth"])),
+            ("$.nested[\"a.b\"]", Some(&body["nested"]["a.b"])),
+            ("$.checks[2]", None),
+            ("$.missing", None),
+        ];
+        for (path, expected) in cases {
+            let path = JsonPath::from_str(path).unwrap();
+            assert_eq!(path.select(&body), expected, "value selected by {path}");
+        }
+    }
+
+    #[test]
+    fn invalid_path_from_str() {
+        let cases = [
+            "status",
+            "$status",
+            "$.",
+            "$..status",
+            "$[0",
+            "$[\"status\"",
+            "$[status]",
+            "$[\"status']",
+            "$[-1]",
+        ];
+        for path in cases {
+            assert!(JsonPath::from_str(path).is_err(), "{path} must be rejected");
+        }
+    }
+
+    #[test]
+    fn assertion_from_str() {
+        let body = json!({"status": "ok", "depth": 10, "ready": true, "queue depth": 3});
+        // Each assertion with whether the body satisfies it
+        let cases = [
+            ("$.status exists", true),
+            ("$.version exists", false),
+            ("$.status == \"ok\"", true),
+            ("$.status == ok", true),
+            ("$.status == \"down\"", false),
+            ("$.ready == true", true),
+            ("$.depth == 10.0", true),
+            ("$.status != \"down\"", true),
+            ("$.depth != 10", false),
+            ("$.depth < 11", true),
+            ("$.depth < 10", false),
+            ("$.depth <= 10", true),
+            ("$.depth <= 9", false),
+            ("$.depth > 9", true),
+            ("$.depth > 10", false),
+            ("$.depth >= 10", true),
+            ("$.depth >= 11", false),
+            ("$.status >= 1", false),
+            ("$.version == 1", false),
+            ("$[\"queue depth\"] exists", true),
+            ("$['queue depth'] < 5", true),
+            ("$[\"queue depth\"] == 3", true),
+            ("$[\"queue  depth\"] exists", false),
+        ];
+        for (assertion, satisfied) in cases {
+            let parsed = JsonAssertion::from_str(assertion).unwrap();
+            assert_eq!(
+                parsed.failure(&body).is_none(),
+                satisfied,
+                "result of {assertion}"
+            );
+        }
+    }
+
+    #[test]
+    fn operator_from_str() {
+        let cases = [
+            ("$.a == 1", "=="),
+            ("$.a != 1", "!="),
+            ("$.a < 1", "<"),
+            ("$.a <= 1", "<="),
+            ("$.a > 1", ">"),
+            ("$.a >= 1", ">="),
+            ("$.a >=1", ">="),
+        ];
+        for (assertion, operator) in cases {
+            let Ok(JsonAssertion::Compare(_, comparison, _)) = JsonAssertion::from_str(assertion)
+            else {
+                panic!("{assertion} must be a comparison");
+            };
+            assert_eq!(comparison.to_string(), operator, "operator of {assertion}");
+        }
+    }
+
+    #[test]
+    fn invalid_assertion_from_str() {
+        let cases = [
+            "$.a",
+            "$.a 1",
+            "$.a ==",
+            "$.a === 1",
+            "$.a => 1",
+            "$.a <> 1",
+            "$.a = 1",
+            "$.a < \"1\"",
+            "$.a >= ok",
+            "a == 1",
+        ];
+        for assertion in cases {
+            assert!(
+                JsonAssertion::from_str(assertion).is_err(),
+                "{assertion} must be rejected"
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{str::FromStr, time::Duration};
 
 use yaml_rust2::Yaml;
 
+pub mod json_assertion;
 pub mod server;
 pub mod structs;
 pub mod web;
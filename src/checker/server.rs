@@ -1,9 +1,11 @@
 use std::{
-    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    io,
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
     str::FromStr,
     time::Duration,
 };
 
+use tokio::{net::TcpStream, time};
 use yaml_rust2::Yaml;
 
 use crate::{
@@ -25,10 +27,11 @@ impl ServerChecker {
     }
 
     pub async fn check(&self, service: &str) -> CheckerResult {
-        let stream = if self.timeout.is_some() {
-            TcpStream::connect_timeout(&self.host, self.timeout.unwrap())
-        } else {
-            TcpStream::connect(self.host)
+        let stream = match self.timeout {
+            Some(timeout) => time::timeout(timeout, TcpStream::connect(self.host))
+                .await
+                .unwrap_or_else(|_| Err(io::Error::from(io::ErrorKind::TimedOut))),
+            None => TcpStream::connect(self.host).await,
         };
         match stream {
             Ok(_) => CheckerResult::new(
@@ -1,5 +1,8 @@
 use std::{fmt::Display, str::FromStr};
 
+use jiff::Zoned;
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug)]
 pub enum CheckerType {
     Web,
@@ -24,17 +27,18 @@ impl FromStr for CheckerType {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckerStatus {
     Success,
     Error,
     Timeout,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckerResult {
     pub service_name: String,
     pub status: CheckerStatus,
     pub message: String,
+    pub checked_at: Zoned,
 }
 impl CheckerResult {
     pub fn new(service_name: String, status: CheckerStatus, message: String) -> Self {
@@ -42,6 +46,7 @@ impl CheckerResult {
             service_name,
             status,
             message,
+            checked_at: Zoned::now(),
         }
     }
 }
@@ -1,37 +1,155 @@
-use std::{str::FromStr, time::Duration};
+use std::{fs, ops::RangeInclusive, str::FromStr, time::Duration};
 
+use regex::Regex;
 use reqwest::{
-    Client, RequestBuilder, StatusCode,
+    Client, Method, RequestBuilder, Response, StatusCode,
     header::{HeaderMap, HeaderName, HeaderValue},
 };
+use serde_json::Value;
 use yaml_rust2::Yaml;
 
 use crate::{
     checker::{
-        error::CheckerParseError, structs::{CheckerResult, CheckerStatus, CheckerType}, Checker
+        error::CheckerParseError, json_assertion::JsonAssertion,
+        structs::{CheckerResult, CheckerStatus, CheckerType}, Checker
     },
     parser::{keys::ConfigKeyInvalidFormat, ConfigKey},
 };
 
+/// A condition on the response body, checked once the status code matched.
+#[derive(Debug)]
+enum BodyAssertion {
+    Contains(String),
+    NotContains(String),
+    Regex(Regex),
+}
+impl BodyAssertion {
+    /// Describe why the `body` doesn't satisfy the assertion, if it doesn't.
+    fn failure(&self, body: &str) -> Option<String> {
+        match self {
+            BodyAssertion::Contains(text) if !body.contains(text.as_str()) => {
+                Some(format!("the body does not contain \"{text}\""))
+            }
+            BodyAssertion::NotContains(text) if body.contains(text.as_str()) => {
+                Some(format!("the body contains \"{text}\""))
+            }
+            BodyAssertion::Regex(regex) if !regex.is_match(body) => {
+                Some(format!("the body does not match the regex \"{regex}\""))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The status codes accepted by a web check, each one a single code or an inclusive range.
+#[derive(Debug)]
+pub struct ExpectedCodes(Vec<RangeInclusive<u16>>);
+impl ExpectedCodes {
+    pub fn contains(&self, status: StatusCode) -> bool {
+        self.0.iter().any(|codes| codes.contains(&status.as_u16()))
+    }
+
+    /// Read a code like `200`, a class like `"2xx"` or a range like `"200-299"`.
+    fn entry(value: &Yaml) -> Option<RangeInclusive<u16>> {
+        let code = |code: &str| {
+            code.trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|code| StatusCode::from_u16(*code).is_ok())
+        };
+        match value {
+            Yaml::Integer(value) => {
+                let value = u16::try_from(*value).ok()?;
+                StatusCode::from_u16(value).ok().map(|_| value..=value)
+            }
+            Yaml::String(value) => {
+                let value = value.trim().to_lowercase();
+                if let Some(class) = value.strip_suffix("xx") {
+                    let class = class
+                        .parse::<u16>()
+                        .ok()
+                        .filter(|class| (1..=9).contains(class))?;
+                    let start = class * 100;
+                    Some(start..=start + 99)
+                } else if let Some((start, end)) = value.split_once('-') {
+                    let (start, end) = (code(start)?, code(end)?);
+                    (start <= end).then_some(start..=end)
+                } else {
+                    code(&value).map(|code| code..=code)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+impl TryFrom<&Yaml> for ExpectedCodes {
+    type Error = CheckerParseError;
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let entries = match data {
+            Yaml::Array(values) if !values.is_empty() => {
+                values.iter().map(ExpectedCodes::entry).collect()
+            }
+            Yaml::Array(_) => None,
+            value => ExpectedCodes::entry(value).map(|codes| vec![codes]),
+        };
+
+        entries.map(ExpectedCodes).ok_or_else(|| {
+            CheckerParseError::InvalidFormat(
+                ConfigKey::ExpectedHttpCode,
+                ConfigKeyInvalidFormat::new(ConfigKey::ExpectedHttpCode),
+            )
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct WebCheckerBuilder {
     req_builder: RequestBuilder,
-    expected_code: StatusCode,
+    expected_codes: ExpectedCodes,
+    body_assertions: Vec<BodyAssertion>,
+    json_assertions: Vec<JsonAssertion>,
 }
 
 impl WebCheckerBuilder {
-    fn new(url: &str, expected_code: StatusCode) -> Self {
+    fn new(url: &str, method: Method, expected_codes: ExpectedCodes) -> Self {
         Self {
-            req_builder: Client::new().get(url),
-            expected_code,
+            req_builder: Client::new().request(method, url),
+            expected_codes,
+            body_assertions: vec![],
+            json_assertions: vec![],
         }
     }
 
+    fn body_assertions(mut self, body_assertions: Vec<BodyAssertion>) -> Self {
+        self.body_assertions = body_assertions;
+        self
+    }
+
+    fn json_assertions(mut self, json_assertions: Vec<JsonAssertion>) -> Self {
+        self.json_assertions = json_assertions;
+        self
+    }
+
     fn headers(mut self, headers: HeaderMap) -> Self {
         self.req_builder = self.req_builder.headers(headers);
         self
     }
 
+    fn body(mut self, body: String) -> Self {
+        self.req_builder = self.req_builder.body(body);
+        self
+    }
+
+    fn basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.req_builder = self.req_builder.basic_auth(username, Some(password));
+        self
+    }
+
+    fn bearer_auth(mut self, token: &str) -> Self {
+        self.req_builder = self.req_builder.bearer_auth(token);
+        self
+    }
+
     fn timeout(mut self, timeout: Duration) -> Self {
         self.req_builder = self.req_builder.timeout(timeout);
         self
@@ -40,7 +158,9 @@ impl WebCheckerBuilder {
     fn build(self) -> WebChecker {
         WebChecker {
             req_builder: self.req_builder,
-            expected_code: self.expected_code,
+            expected_codes: self.expected_codes,
+            body_assertions: self.body_assertions,
+            json_assertions: self.json_assertions,
         }
     }
 }
@@ -48,28 +168,26 @@ impl WebCheckerBuilder {
 #[derive(Debug)]
 pub struct WebChecker {
     req_builder: RequestBuilder,
-    expected_code: StatusCode,
+    expected_codes: ExpectedCodes,
+    body_assertions: Vec<BodyAssertion>,
+    json_assertions: Vec<JsonAssertion>,
 }
 
 impl WebChecker {
-    pub fn builder(url: &str, expected_code: StatusCode) -> WebCheckerBuilder {
-        WebCheckerBuilder::new(url, expected_code)
+    pub fn builder(url: &str, method: Method, expected_codes: ExpectedCodes) -> WebCheckerBuilder {
+        WebCheckerBuilder::new(url, method, expected_codes)
     }
 
-    pub fn expected_code(&self) -> &StatusCode {
-        &self.expected_code
+    pub fn expected_codes(&self) -> &ExpectedCodes {
+        &self.expected_codes
     }
 
     pub async fn check(&self, service: &str) -> CheckerResult {
         let response = self.req_builder.try_clone().unwrap().send().await;
         match response {
             std::result::Result::Ok(response) => {
-                if response.status() == self.expected_code {
-                    CheckerResult::new(
-                        service.to_string(),
-                        CheckerStatus::Success,
-                        format!("Service available with status {}", response.status()),
-                    )
+                if self.expected_codes.contains(response.status()) {
+                    self.check_body(service, response).await
                 } else {
                     CheckerResult::new(
                         service.to_string(),
@@ -85,6 +203,201 @@ impl WebChecker {
             ),
         }
     }
+
+    /// Apply the body and JSON assertions, the body is only read when there are some.
+    async fn check_body(&self, service: &str, response: Response) -> CheckerResult {
+        let status = response.status();
+        if self.body_assertions.is_empty() && self.json_assertions.is_empty() {
+            return CheckerResult::new(
+                service.to_string(),
+                CheckerStatus::Success,
+                format!("Service available with status {status}"),
+            );
+        }
+
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(err) => {
+                return CheckerResult::new(
+                    service.to_string(),
+                    CheckerStatus::Error,
+                    format!("Service unavailable, unable to read the body: {err}"),
+                );
+            }
+        };
+
+        match self
+            .body_assertions
+            .iter()
+            .find_map(|assertion| assertion.failure(&body))
+            .or_else(|| self.json_failure(&body))
+        {
+            Some(failure) => CheckerResult::new(
+                service.to_string(),
+                CheckerStatus::Error,
+                format!("Service responded with status {status} but {failure}"),
+            ),
+            None => CheckerResult::new(
+                service.to_string(),
+                CheckerStatus::Success,
+                format!("Service available with status {status} and the expected body"),
+            ),
+        }
+    }
+
+    fn json_failure(&self, body: &str) -> Option<String> {
+        if self.json_assertions.is_empty() {
+            return None;
+        }
+
+        match serde_json::from_str::<Value>(body) {
+            Ok(json) => self
+                .json_assertions
+                .iter()
+                .find_map(|assertion| assertion.failure(&json)),
+            Err(err) => Some(format!("the body is not valid JSON: {err}")),
+        }
+    }
+
+    /// Read a key accepting a single string or an array of them.
+    fn strings(data: &Yaml, key: ConfigKey) -> Result<Vec<String>, CheckerParseError> {
+        let invalid_format =
+            || CheckerParseError::InvalidFormat(key, ConfigKeyInvalidFormat::new(key));
+        match &data[key.as_ref()] {
+            Yaml::String(value) if !value.is_empty() => Ok(vec![value.clone()]),
+            Yaml::Array(values) => values
+                .iter()
+                .map(|value| match value {
+                    Yaml::String(value) if !value.is_empty() => Ok(value.clone()),
+                    _ => Err(invalid_format()),
+                })
+                .collect(),
+            Yaml::BadValue => Ok(vec![]),
+            _ => Err(invalid_format()),
+        }
+    }
+
+    fn body_assertions(data: &Yaml) -> Result<Vec<BodyAssertion>, CheckerParseError> {
+        let mut body_assertions: Vec<BodyAssertion> =
+            WebChecker::strings(data, ConfigKey::BodyContains)?
+                .into_iter()
+                .map(BodyAssertion::Contains)
+                .collect();
+        body_assertions.extend(
+            WebChecker::strings(data, ConfigKey::BodyNotContains)?
+                .into_iter()
+                .map(BodyAssertion::NotContains),
+        );
+        for pattern in WebChecker::strings(data, ConfigKey::BodyRegex)? {
+            let regex = Regex::new(&pattern).map_err(|_| {
+                CheckerParseError::InvalidFormat(
+                    ConfigKey::BodyRegex,
+                    ConfigKeyInvalidFormat::new(ConfigKey::BodyRegex),
+                )
+            })?;
+            body_assertions.push(BodyAssertion::Regex(regex));
+        }
+
+        Ok(body_assertions)
+    }
+
+    fn method(data: &Yaml) -> Result<Method, CheckerParseError> {
+        let invalid_format = || {
+            CheckerParseError::InvalidFormat(
+                ConfigKey::Method,
+                ConfigKeyInvalidFormat::new(ConfigKey::Method),
+            )
+        };
+        match &data[ConfigKey::Method.as_ref()] {
+            Yaml::String(method) => {
+                Method::from_str(&method.to_uppercase()).map_err(|_| invalid_format())
+            }
+            Yaml::BadValue => Ok(Method::GET),
+            _ => Err(invalid_format()),
+        }
+    }
+
+    fn optional_str(data: &Yaml, key: ConfigKey) -> Result<Option<&str>, CheckerParseError> {
+        match &data[key.as_ref()] {
+            Yaml::String(value) if !value.is_empty() => Ok(Some(value)),
+            Yaml::BadValue => Ok(None),
+            _ => Err(CheckerParseError::InvalidFormat(
+                key,
+                ConfigKeyInvalidFormat::new(key),
+            )),
+        }
+    }
+
+    fn read_file(path: &str) -> Result<String, CheckerParseError> {
+        fs::read_to_string(path)
+            .map_err(|e| CheckerParseError::UnableToReadFile(path.to_string(), e))
+    }
+
+    /// Read the secret in the first line of the file which path is at `key`,
+    /// so the credentials are kept out of the config file.
+    fn secret_file(data: &Yaml, key: ConfigKey) -> Result<Option<String>, CheckerParseError> {
+        let Some(path) = WebChecker::optional_str(data, key)? else {
+            return Ok(None);
+        };
+
+        match WebChecker::read_file(path)?.lines().next().map(str::trim) {
+            Some(secret) if !secret.is_empty() => Ok(Some(secret.to_string())),
+            _ => Err(CheckerParseError::InternalParse(format!(
+                "The file in '{key}' cannot be empty"
+            ))),
+        }
+    }
+
+    fn body(data: &Yaml) -> Result<Option<String>, CheckerParseError> {
+        match (
+            WebChecker::optional_str(data, ConfigKey::Body)?,
+            WebChecker::optional_str(data, ConfigKey::BodyFile)?,
+        ) {
+            (Some(_), Some(_)) => Err(CheckerParseError::InternalParse(format!(
+                "Keys '{}' and '{}' cannot be used together",
+                ConfigKey::Body,
+                ConfigKey::BodyFile
+            ))),
+            (Some(body), None) => Ok(Some(body.to_string())),
+            (None, Some(path)) => WebChecker::read_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    fn basic_auth(data: &Yaml) -> Result<Option<(String, String)>, CheckerParseError> {
+        let basic_auth = &data[ConfigKey::BasicAuth.as_ref()];
+        let invalid_format = || {
+            CheckerParseError::InvalidFormat(
+                ConfigKey::BasicAuth,
+                ConfigKeyInvalidFormat::new(ConfigKey::BasicAuth),
+            )
+        };
+        match basic_auth {
+            Yaml::Hash(_) => {
+                let username = WebChecker::optional_str(basic_auth, ConfigKey::Username)?
+                    .ok_or_else(invalid_format)?;
+                let password = WebChecker::secret_file(basic_auth, ConfigKey::PasswordFile)?
+                    .ok_or_else(invalid_format)?;
+                Ok(Some((username.to_string(), password)))
+            }
+            Yaml::BadValue => Ok(None),
+            _ => Err(invalid_format()),
+        }
+    }
+
+    fn json_assertions(data: &Yaml) -> Result<Vec<JsonAssertion>, CheckerParseError> {
+        WebChecker::strings(data, ConfigKey::JsonAssertions)?
+            .iter()
+            .map(|assertion| {
+                JsonAssertion::from_str(assertion).map_err(|err| {
+                    CheckerParseError::InternalParse(format!(
+                        "Invalid format for '{}'. {err}",
+                        ConfigKey::JsonAssertions
+                    ))
+                })
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<&Yaml> for WebChecker {
@@ -100,26 +413,33 @@ impl TryFrom<&Yaml> for WebChecker {
             }
         };
 
-        let expected_http_code = match data[ConfigKey::ExpectedHttpCode.as_ref()] {
-            Yaml::Integer(http_code)
-                if http_code >= u16::MIN as i64 && http_code <= u16::MAX as i64 =>
-            {
-                StatusCode::from_u16(http_code as u16).map_err(|_| {
-                    CheckerParseError::InvalidFormat(
-                        ConfigKey::ExpectedHttpCode,
-                        ConfigKeyInvalidFormat::new(ConfigKey::ExpectedHttpCode),
-                    )
-                })?
+        let expected_codes = ExpectedCodes::try_from(&data[ConfigKey::ExpectedHttpCode.as_ref()])?;
+
+        let method = WebChecker::method(data)?;
+        let mut web_checker = WebChecker::builder(url, method, expected_codes)
+            .body_assertions(WebChecker::body_assertions(data)?)
+            .json_assertions(WebChecker::json_assertions(data)?);
+
+        if let Some(body) = WebChecker::body(data)? {
+            web_checker = web_checker.body(body);
+        }
+
+        let basic_auth = WebChecker::basic_auth(data)?;
+        let bearer_token = WebChecker::secret_file(data, ConfigKey::BearerTokenFile)?;
+        match (basic_auth, bearer_token) {
+            (Some(_), Some(_)) => {
+                return Err(CheckerParseError::InternalParse(format!(
+                    "Keys '{}' and '{}' cannot be used together",
+                    ConfigKey::BasicAuth,
+                    ConfigKey::BearerTokenFile
+                )));
             }
-            _ => {
-                return Err(CheckerParseError::InvalidFormat(
-                    ConfigKey::ExpectedHttpCode,
-                    ConfigKeyInvalidFormat::new(ConfigKey::ExpectedHttpCode),
-                ));
+            (Some((username, password)), None) => {
+                web_checker = web_checker.basic_auth(&username, &password);
             }
-        };
-
-        let mut web_checker = WebChecker::builder(url, expected_http_code);
+            (None, Some(token)) => web_checker = web_checker.bearer_auth(&token),
+            (None, None) => {}
+        }
 
         if let Some(timeout) = Checker::timeout(data)? {
             web_checker = web_checker.timeout(timeout);
@@ -155,3 +475,52 @@ impl TryFrom<&Yaml> for WebChecker {
         Ok(web_checker.build())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use yaml_rust2::YamlLoader;
+
+    use super::*;
+
+    #[test]
+    fn expected_codes_from_yaml() {
+        let cases: &[(&str, Option<Vec<RangeInclusive<u16>>>)] = &[
+            ("200", Some(vec![200..=200])),
+            ("'204'", Some(vec![204..=204])),
+            ("2xx", Some(vec![200..=299])),
+            ("5XX", Some(vec![500..=599])),
+            ("200-299", Some(vec![200..=299])),
+            ("' 200 - 204 '", Some(vec![200..=204])),
+            ("[200, 3xx]", Some(vec![200..=200, 300..=399])),
+            ("[200, '201-204']", Some(vec![200..=200, 201..=204])),
+            ("299-200", None),
+            ("0xx", None),
+            ("10xx", None),
+            ("xx", None),
+            ("99", None),
+            ("1000", None),
+            ("-1", None),
+            ("200-", None),
+            ("200-1000", None),
+            ("ok", None),
+            ("[]", None),
+            ("[200, ok]", None),
+            ("{code: 200}", None),
+        ];
+        for (yaml, expected) in cases {
+            let data = &YamlLoader::load_from_str(yaml).unwrap()[0];
+            let codes = ExpectedCodes::try_from(data).ok().map(|codes| codes.0);
+            assert_eq!(&codes, expected, "expected codes parsed from {yaml}");
+        }
+    }
+
+    #[test]
+    fn expected_codes_contain_a_status() {
+        let data = &YamlLoader::load_from_str("[204, 3xx]").unwrap()[0];
+        let codes = ExpectedCodes::try_from(data).unwrap();
+        assert!(codes.contains(StatusCode::NO_CONTENT));
+        assert!(codes.contains(StatusCode::MOVED_PERMANENTLY));
+        assert!(!codes.contains(StatusCode::OK));
+        assert!(!codes.contains(StatusCode::NOT_FOUND));
+    }
+}
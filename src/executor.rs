@@ -1,45 +1,54 @@
-use std::sync::mpsc::Sender;
+use std::time::Duration;
 
+use tokio::{sync::mpsc::UnboundedSender, time};
 use tracing::{Level, event};
 
-use crate::{
-    checker::{
-        Checker,
-        structs::{CheckerResult, CheckerStatus},
-    },
-    task::Task,
-};
+use crate::{checker::structs::CheckerStatus, notification::Notification, task::Task};
 
 /// This function calls the checker of the received function.
-/// Validate the result, if necessary send the result to the notifier thread.
-/// Finally it sents the task back to the enqueuer thread.
+/// A failed check is retried following the retry policy of the task,
+/// and only a change of the task state is sent to the notifier worker,
+/// a failure confirmed by the failure threshold, an escalation or the recovery of a down service.
+/// Finally it sents the task back to the enqueuer worker.
 pub async fn execute_check(
     mut task: Task,
-    tx_task: Sender<Task>,
-    tx_notifier: Sender<CheckerResult>,
+    tx_task: UnboundedSender<Task>,
+    tx_notifier: UnboundedSender<Notification>,
 ) {
     task.set_last_execution_at();
-    let checker_result = match task.checker() {
-        Checker::Web(checker) => checker.check(&task.name()).await,
-        Checker::Server(checker) => checker.check(&task.name()).await,
-    };
-    task.log(&checker_result);
-    if checker_result.status != CheckerStatus::Success
-        && let Err(err) = tx_notifier.send(checker_result)
-    {
+    let mut checker_result = task.check().await;
+    let mut retries = 0;
+    while checker_result.status != CheckerStatus::Success && retries < task.retry_policy().retries {
+        task.log(&checker_result);
+        retries += 1;
         event!(
-            Level::ERROR,
-            message = ?err.0,
-            error = %err,
-            "Error sending the checker result to notifier thread"
+            Level::DEBUG,
+            service = task.name(),
+            retry = retries,
+            "Retrying a failed check"
         );
+        time::sleep(Duration::try_from(task.retry_policy().retry_interval).unwrap_or_default())
+            .await;
+        checker_result = task.check().await;
+    }
+    task.log(&checker_result);
+
+    for notification in task.register_result(&checker_result) {
+        if let Err(err) = tx_notifier.send(notification) {
+            event!(
+                Level::ERROR,
+                message = ?err.0,
+                error = %err,
+                "Error sending the notification to notifier worker"
+            );
+        }
     }
     if let Err(err) = tx_task.send(task) {
         event!(
             Level::ERROR,
             message = ?err.0,
             error = %err,
-            "Error sending task to the enqueuer thread"
+            "Error sending task to the enqueuer worker"
         );
     }
 }
@@ -23,9 +23,11 @@ async fn main() -> Result<(), Box<dyn ConfigError>> {
 async fn entrypoint() -> Result<(), Box<dyn ConfigError>> {
     let args = Args::parse();
 
-    let config = Configuration::builder(&args)?
+    let mut config = Configuration::builder(&args)?
         .services()?
-        .mailer()?
+        .notifiers()?
+        .outbox()?
+        .digest()?
         .build()?;
 
     event!(
@@ -33,11 +35,7 @@ async fn entrypoint() -> Result<(), Box<dyn ConfigError>> {
         temp_dir_path = std::env::temp_dir().to_str().unwrap()
     );
 
-    let mut notifiers: Vec<_> = vec![];
-    if let Some(mailer) = &config.mailer {
-        notifiers.push(mailer.clone());
-    }
-
+    let notifiers = std::mem::take(&mut config.notifiers);
     let scheduler = Scheduler::new(config);
     scheduler.init(notifiers).await;
 
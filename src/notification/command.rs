@@ -0,0 +1,125 @@
+use std::{io, process::Stdio, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{io::AsyncWriteExt, process::Command, time};
+use tracing::{Level, event};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs an executable for each notification.
+/// The notification is available in `TOKTOK_*` environment variables, and as JSON on the stdin.
+#[derive(Debug, Clone)]
+pub struct CommandNotifier {
+    path: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl CommandNotifier {
+    async fn run(
+        &self,
+        notification: &Notification,
+    ) -> Result<std::process::Output, NotificationError> {
+        let exec_result = &notification.result;
+        let mut child = Command::new(&self.path)
+            .args(&self.args)
+            .env("TOKTOK_SERVICE", &exec_result.service_name)
+            .env("TOKTOK_STATUS", exec_result.status.to_string())
+            .env("TOKTOK_MESSAGE", &exec_result.message)
+            .env("TOKTOK_EVENT", notification.kind.to_string())
+            .env(
+                "TOKTOK_CHECKED_AT",
+                exec_result.checked_at.timestamp().to_string(),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            // The error kind is kept, so a missing or not executable path is not retried
+            .map_err(|e| {
+                NotificationError::Io(io::Error::new(
+                    e.kind(),
+                    format!("Unable to start the command: {e}"),
+                ))
+            })?;
+
+        let stdin = child.stdin.take();
+        let payload = notification.payload().to_string();
+        let write_payload = async move {
+            if let Some(mut stdin) = stdin {
+                // A command which doesn't read the stdin is not an error.
+                let _ = stdin.write_all(payload.as_bytes()).await;
+            }
+        };
+
+        // The payload is written within the timeout, as it blocks once the pipe is full
+        // when the command never reads it.
+        time::timeout(self.timeout, async {
+            let (_, output) = tokio::join!(write_payload, child.wait_with_output());
+            output
+        })
+        .await
+        .map_err(|_| {
+            NotificationError::Failed(format!("The command timed out after {:?}", self.timeout))
+        })?
+        .map_err(|e| NotificationError::Failed(format!("Unable to wait for the command: {e}")))
+    }
+}
+
+#[async_trait]
+impl Notifier for CommandNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let output = self.run(notification).await?;
+        let exit_code = output.status.code();
+        event!(
+            Level::DEBUG,
+            exit_code,
+            stdout = %String::from_utf8_lossy(&output.stdout).trim(),
+            stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+            "Command notification output"
+        );
+        if output.status.success() {
+            event!(
+                Level::INFO,
+                exit_code,
+                "Command notification executed successfully"
+            );
+            return Ok(());
+        }
+
+        event!(
+            Level::ERROR,
+            exit_code,
+            "Command notification exited with failure"
+        );
+
+        Err(NotificationError::Failed(format!(
+            "The command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+impl TryFrom<&Yaml> for CommandNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        Ok(CommandNotifier {
+            path: fields::required_str(data, ConfigKey::Path, NotificationType::Command)?,
+            args: fields::optional_list(data, ConfigKey::Args)?,
+            timeout: fields::timeout(data, DEFAULT_TIMEOUT)?,
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use std::{collections::VecDeque, time::Duration};
+
+use jiff::Timestamp;
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{error::NotificationParseError, fields},
+    parser::ConfigKey,
+};
+
+const DEFAULT_WINDOW: Duration = Duration::from_secs(30);
+const DEFAULT_RATE_PERIOD: Duration = Duration::from_secs(60);
+
+/// How the notifications are grouped during a storm, defined by the optional `digest` section.
+/// A notification waits `window` for the next ones, then all the notifications pending
+/// for a notifier are sent as a single digest.
+/// Each notifier sends at most `rate_limit` messages every `rate_period`.
+#[derive(Debug, Clone)]
+pub struct DigestPolicy {
+    pub window: Duration,
+    pub rate_limit: Option<usize>,
+    pub rate_period: Duration,
+}
+
+impl Default for DigestPolicy {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            rate_limit: None,
+            rate_period: DEFAULT_RATE_PERIOD,
+        }
+    }
+}
+
+impl TryFrom<&Yaml> for DigestPolicy {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let mut policy = DigestPolicy::default();
+        if let Some(window) = fields::optional_positive(data, ConfigKey::Window)? {
+            policy.window = Duration::from_secs(window);
+        }
+        if let Some(rate_limit) = fields::optional_positive(data, ConfigKey::RateLimit)? {
+            policy.rate_limit = Some(rate_limit as usize);
+        }
+        if let Some(rate_period) = fields::optional_positive(data, ConfigKey::RatePeriod)? {
+            policy.rate_period = Duration::from_secs(rate_period);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Tracks the messages sent by a notifier, to hold the next one while the rate limit is reached.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    limit: Option<usize>,
+    period: Duration,
+    sent_at: VecDeque<Timestamp>,
+}
+
+impl RateLimiter {
+    pub fn new(policy: Option<&DigestPolicy>) -> Self {
+        Self {
+            limit: policy.and_then(|policy| policy.rate_limit),
+            period: policy.map(|policy| policy.rate_period).unwrap_or_default(),
+            sent_at: VecDeque::new(),
+        }
+    }
+
+    /// When the next message can be sent, the messages older than the period are forgotten.
+    pub fn available_at(&mut self) -> Timestamp {
+        let now = Timestamp::now();
+        while self
+            .sent_at
+            .front()
+            .is_some_and(|sent_at| *sent_at + self.period <= now)
+        {
+            self.sent_at.pop_front();
+        }
+
+        match (self.limit, self.sent_at.front()) {
+            (Some(limit), Some(oldest)) if self.sent_at.len() >= limit => *oldest + self.period,
+            _ => now,
+        }
+    }
+
+    pub fn record(&mut self) {
+        if self.limit.is_some() {
+            self.sent_at.push_back(Timestamp::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::section;
+
+    #[test]
+    fn policy_from_yaml() {
+        let policy =
+            DigestPolicy::try_from(&section("window: 10\nrate_limit: 5\nrate_period: 120"))
+                .unwrap();
+        assert_eq!(policy.window, Duration::from_secs(10));
+        assert_eq!(policy.rate_limit, Some(5));
+        assert_eq!(policy.rate_period, Duration::from_secs(120));
+
+        let default = DigestPolicy::try_from(&section("{}")).unwrap();
+        assert_eq!(default.window, DEFAULT_WINDOW);
+        assert_eq!(default.rate_limit, None);
+        assert_eq!(default.rate_period, DEFAULT_RATE_PERIOD);
+
+        for invalid in ["window: 0", "rate_limit: -1", "rate_period: ten"] {
+            assert!(
+                DigestPolicy::try_from(&section(invalid)).is_err(),
+                "{invalid} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn without_limit_a_message_is_always_available() {
+        for policy in [None, Some(&DigestPolicy::default())] {
+            let mut limiter = RateLimiter::new(policy);
+            for _ in 0..100 {
+                limiter.record();
+            }
+            assert!(limiter.available_at() <= Timestamp::now());
+        }
+    }
+
+    #[test]
+    fn limit_holds_the_next_message_until_the_oldest_leaves_the_period() {
+        let policy = DigestPolicy {
+            rate_limit: Some(2),
+            rate_period: Duration::from_millis(200),
+            ..DigestPolicy::default()
+        };
+        let mut limiter = RateLimiter::new(Some(&policy));
+        limiter.record();
+        assert!(limiter.available_at() <= Timestamp::now());
+
+        limiter.record();
+        let oldest = limiter.sent_at[0];
+        assert_eq!(limiter.available_at(), oldest + policy.rate_period);
+
+        std::thread::sleep(policy.rate_period);
+        assert!(limiter.available_at() <= Timestamp::now());
+        assert!(limiter.sent_at.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::{Value, json};
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+/// Sends the notification to a Discord webhook as an embed.
+#[derive(Debug, Clone)]
+pub struct DiscordNotifier {
+    client: Client,
+    webhook_url: Url,
+    username: Option<String>,
+    avatar_url: Option<String>,
+}
+
+impl DiscordNotifier {
+    fn color(status: &CheckerStatus) -> u32 {
+        match status {
+            CheckerStatus::Success => 0x2eb67d,
+            CheckerStatus::Error => 0xe01e5a,
+            CheckerStatus::Timeout => 0xecb22e,
+        }
+    }
+
+    fn payload(&self, notification: &Notification) -> Value {
+        let exec_result = &notification.result;
+        let mut fields = vec![
+            json!({ "name": "Service", "value": exec_result.service_name, "inline": true }),
+            json!({ "name": "Status", "value": exec_result.status.to_string(), "inline": true }),
+        ];
+        if let Some(outage) = notification.outage() {
+            fields
+                .push(json!({ "name": "Outage", "value": format!("{outage:#}"), "inline": true }));
+        }
+
+        let mut payload = json!({
+            "embeds": [{
+                "title": notification.title(),
+                "description": exec_result.message,
+                "color": DiscordNotifier::color(&exec_result.status),
+                "fields": fields,
+                "timestamp": exec_result.checked_at.timestamp().to_string(),
+            }],
+        });
+
+        if let Some(username) = &self.username {
+            payload["username"] = json!(username);
+        }
+        if let Some(avatar_url) = &self.avatar_url {
+            payload["avatar_url"] = json!(avatar_url);
+        }
+
+        payload
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let request = self
+            .client
+            .post(self.webhook_url.clone())
+            .json(&self.payload(notification));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for DiscordNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        Ok(DiscordNotifier {
+            client: http::client(data)?,
+            webhook_url: http::url(data, ConfigKey::WebhookUrl, NotificationType::Discord)?,
+            username: fields::optional_str(data, ConfigKey::Username)?,
+            avatar_url: fields::optional_str(data, ConfigKey::AvatarUrl)?,
+        })
+    }
+}
@@ -0,0 +1,473 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use jiff::Timestamp;
+use tokio::{
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    time,
+};
+use tracing::{Level, event};
+
+use crate::notification::{
+    NamedNotifier, Notification,
+    digest::{DigestPolicy, RateLimiter},
+    outbox::{Outbox, OutboxEntry, OutboxPolicy},
+};
+
+/// Delivers each notification to the notifiers it's routed to.
+/// Every notifier runs in its own worker, so a slow one doesn't delay the others,
+/// while the notifications of a notifier are still sent in the order they arrived.
+pub struct Dispatcher {
+    workers: Vec<(String, UnboundedSender<Notification>)>,
+}
+
+impl Dispatcher {
+    /// Spawn a worker for each notifier, with the notifications left pending in the outbox.
+    /// It must be called inside the tokio runtime.
+    pub fn start(
+        notifiers: Vec<NamedNotifier>,
+        policy: OutboxPolicy,
+        digest: Option<DigestPolicy>,
+    ) -> Self {
+        let outbox = Arc::new(Outbox::load(policy.path.clone()));
+        let names: Vec<String> = notifiers.iter().map(|named| named.name.clone()).collect();
+        outbox.retain(&names);
+
+        let policy = Arc::new(policy);
+        let digest = digest.map(Arc::new);
+        let workers = notifiers
+            .into_iter()
+            .map(|named| {
+                let name = named.name.clone();
+                let (tx, rx) = unbounded_channel::<Notification>();
+                let queue = outbox.take(&name);
+                let worker = NotifierWorker {
+                    queue,
+                    named,
+                    outbox: outbox.clone(),
+                    policy: policy.clone(),
+                    limiter: RateLimiter::new(digest.as_deref()),
+                    digest: digest.clone(),
+                };
+                tokio::spawn(worker.run(rx));
+                (name, tx)
+            })
+            .collect();
+
+        Self { workers }
+    }
+
+    pub fn dispatch(&self, notification: Notification) {
+        for (name, tx) in self
+            .workers
+            .iter()
+            .filter(|(name, _)| notification.is_routed_to(name))
+        {
+            if let Err(err) = tx.send(notification.clone()) {
+                event!(
+                    Level::ERROR,
+                    notifier = name,
+                    error = %err,
+                    "Error sending the notification to the notifier worker"
+                );
+            }
+        }
+    }
+}
+
+/// Sends the notifications of a notifier one by one, in the order they arrived.
+/// A failed notification waits for its new attempt holding back only the next ones
+/// of its service, so a recovery is never sent before the failure it resolves.
+/// It's dropped when the error can't be solved by a new attempt.
+/// With a digest policy, the notifications waiting are merged and sent as a single message.
+struct NotifierWorker {
+    named: NamedNotifier,
+    queue: VecDeque<OutboxEntry>,
+    outbox: Arc<Outbox>,
+    policy: Arc<OutboxPolicy>,
+    digest: Option<Arc<DigestPolicy>>,
+    limiter: RateLimiter,
+}
+
+impl NotifierWorker {
+    async fn run(mut self, mut rx: UnboundedReceiver<Notification>) {
+        loop {
+            self.drop_expired();
+            let Some(due_at) = self.due_at() else {
+                match rx.recv().await {
+                    Some(notification) => self.push(notification),
+                    None => return,
+                }
+                continue;
+            };
+
+            let ready_at = due_at.max(self.limiter.available_at());
+            let wait =
+                Duration::try_from(Timestamp::now().duration_until(ready_at)).unwrap_or_default();
+            if !wait.is_zero() {
+                tokio::select! {
+                    _ = time::sleep(wait) => {}
+                    notification = rx.recv() => match notification {
+                        Some(notification) => self.push(notification),
+                        None => return,
+                    },
+                }
+                continue;
+            }
+
+            let indexes = self.ready_indexes();
+            let notification = self.message(&indexes);
+            self.limiter.record();
+            match self.named.notify(&notification).await {
+                Ok(()) => {
+                    self.remove(&indexes);
+                    self.save();
+                }
+                Err(err) if !err.is_retryable() => {
+                    event!(
+                        Level::ERROR,
+                        notifier = self.named.name,
+                        notifications = indexes.len(),
+                        "Notification dropped, a new attempt would fail the same way"
+                    );
+                    self.remove(&indexes);
+                    self.save();
+                }
+                Err(_) => {
+                    let now = Timestamp::now();
+                    for index in &indexes {
+                        let entry = &mut self.queue[*index];
+                        entry.attempts += 1;
+                        entry.next_attempt_at = now + self.policy.backoff(entry.attempts);
+                    }
+                    let attempts = self.queue[indexes[0]].attempts;
+                    event!(
+                        Level::WARN,
+                        notifier = self.named.name,
+                        notifications = indexes.len(),
+                        attempts,
+                        retry_in = ?self.policy.backoff(attempts),
+                        "Notification kept in the outbox for a new attempt"
+                    );
+                    self.save();
+                }
+            }
+        }
+    }
+
+    /// Drop the notifications not delivered before the outbox max age.
+    fn drop_expired(&mut self) {
+        let len = self.queue.len();
+        let max_age = self.policy.max_age;
+        let name = &self.named.name;
+        self.queue.retain(|entry| {
+            if !entry.is_expired(max_age) {
+                return true;
+            }
+            event!(
+                Level::ERROR,
+                notifier = name,
+                service = entry.notification.result.service_name,
+                event = %entry.notification.kind,
+                attempts = entry.attempts,
+                "Notification dropped, it was not delivered before the outbox max age"
+            );
+            false
+        });
+        if self.queue.len() != len {
+            self.save();
+        }
+    }
+
+    /// When each queued notification can be sent, which is never before
+    /// the older notifications of the same service.
+    fn sendable_at(&self) -> Vec<Timestamp> {
+        let mut sendable_at: Vec<Timestamp> = Vec::with_capacity(self.queue.len());
+        for (index, entry) in self.queue.iter().enumerate() {
+            let service_name = &entry.notification.result.service_name;
+            let previous = (0..index).rev().find(|previous| {
+                &self.queue[*previous].notification.result.service_name == service_name
+            });
+            sendable_at.push(match previous {
+                Some(previous) => entry.next_attempt_at.max(sendable_at[previous]),
+                None => entry.next_attempt_at,
+            });
+        }
+        sendable_at
+    }
+
+    /// When the next notification is due, with a digest policy a notification
+    /// also waits for the window to end, so the next ones are merged with it.
+    fn due_at(&self) -> Option<Timestamp> {
+        let window = self
+            .digest
+            .as_ref()
+            .map(|digest| digest.window)
+            .unwrap_or_default();
+        self.queue
+            .iter()
+            .zip(self.sendable_at())
+            .map(|(entry, sendable_at)| sendable_at.max(entry.queued_at + window))
+            .min()
+    }
+
+    /// The queue indexes of the notifications to send now, the ones waiting for
+    /// a new attempt, or for an older one of their service, are left out.
+    /// All of them are merged with a digest policy.
+    fn ready_indexes(&self) -> Vec<usize> {
+        let now = Timestamp::now();
+        let ready = self
+            .sendable_at()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, sendable_at)| *sendable_at <= now)
+            .map(|(index, _)| index);
+        match self.digest {
+            Some(_) => ready.collect(),
+            None => ready.take(1).collect(),
+        }
+    }
+
+    /// The message holding the notifications at `indexes`, a digest when there are several.
+    fn message(&self, indexes: &[usize]) -> Notification {
+        match indexes {
+            [index] => self.queue[*index].notification.clone(),
+            _ => Notification::digest(
+                indexes
+                    .iter()
+                    .map(|index| self.queue[*index].notification.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    fn remove(&mut self, indexes: &[usize]) {
+        for index in indexes.iter().rev() {
+            self.queue.remove(*index);
+        }
+    }
+
+    /// Queue a new notification, it's persisted right away so a restart doesn't lose it,
+    /// even while it waits for the digest window or while it's being sent.
+    fn push(&mut self, notification: Notification) {
+        self.queue.push_back(OutboxEntry::new(notification));
+        self.save();
+    }
+
+    fn save(&self) {
+        self.outbox.save(&self.named.name, &self.queue);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use jiff::{SignedDuration, Zoned};
+
+    use super::*;
+    use crate::{
+        checker::structs::{CheckerResult, CheckerStatus},
+        notification::{NotificationKind, Notifier, error::NotificationError},
+    };
+
+    /// Records the messages it receives, as `service event`, failing the attempts
+    /// of the ones in `failing` once each, and all the ones of `rejected`.
+    #[derive(Default)]
+    struct Recorder {
+        failing: Mutex<Vec<String>>,
+        rejected: Vec<String>,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn new(failing: &[&str], rejected: &[&str]) -> Self {
+            Self {
+                failing: Mutex::new(failing.iter().map(|f| f.to_string()).collect()),
+                rejected: rejected.iter().map(|r| r.to_string()).collect(),
+                ..Recorder::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Notifier for Recorder {
+        async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+            let message = match notification.digested() {
+                Some(notifications) => notifications
+                    .iter()
+                    .map(|n| format!("{} {}", n.result.service_name, n.kind))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                None => format!("{} {}", notification.result.service_name, notification.kind),
+            };
+            self.received.lock().unwrap().push(message.clone());
+
+            if self.rejected.contains(&message) {
+                return Err(NotificationError::InvalidMessage("Rejected".to_string()));
+            }
+            let mut failing = self.failing.lock().unwrap();
+            match failing.iter().position(|failing| *failing == message) {
+                Some(index) => {
+                    failing.remove(index);
+                    Err(NotificationError::Failed("Temporary failure".to_string()))
+                }
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn notification(service_name: &str, kind: NotificationKind) -> Notification {
+        let status = match kind {
+            NotificationKind::Recovered { .. } => CheckerStatus::Success,
+            _ => CheckerStatus::Error,
+        };
+        let result = CheckerResult::new(service_name.to_string(), status, String::new());
+        Notification::new(result, kind, vec![], None)
+    }
+
+    fn down(service_name: &str) -> Notification {
+        notification(service_name, NotificationKind::Down)
+    }
+
+    fn recovered(service_name: &str) -> Notification {
+        let down_since = Zoned::now();
+        notification(service_name, NotificationKind::Recovered { down_since })
+    }
+
+    /// A worker with a short backoff and its own outbox file, along with what it sent.
+    fn worker(name: &str, recorder: Recorder) -> (NotifierWorker, Arc<Mutex<Vec<String>>>) {
+        let received = recorder.received.clone();
+        let policy = OutboxPolicy {
+            path: std::env::temp_dir()
+                .join("toktok-tests")
+                .join(format!("{name}-{}.json", std::process::id())),
+            backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_millis(200),
+            ..OutboxPolicy::default()
+        };
+        let worker = NotifierWorker {
+            named: NamedNotifier::new(name.to_string(), Box::new(recorder)),
+            queue: VecDeque::new(),
+            outbox: Arc::new(Outbox::load(policy.path.clone())),
+            policy: Arc::new(policy),
+            limiter: RateLimiter::default(),
+            digest: None,
+        };
+        (worker, received)
+    }
+
+    /// Run the worker, sending it the `notifications` and waiting `wait_ms` for it.
+    /// The returned channel keeps the worker running until it's dropped.
+    async fn run(
+        worker: NotifierWorker,
+        notifications: Vec<Notification>,
+        wait_ms: u64,
+    ) -> UnboundedSender<Notification> {
+        let (tx, rx) = unbounded_channel();
+        tokio::spawn(worker.run(rx));
+        for notification in notifications {
+            tx.send(notification).unwrap();
+        }
+        time::sleep(Duration::from_millis(wait_ms)).await;
+        tx
+    }
+
+    #[tokio::test]
+    async fn notifications_are_sent_in_order() {
+        let (worker, received) = worker("in-order", Recorder::default());
+        run(worker, vec![down("web"), down("db"), recovered("web")], 100).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["web down", "db down", "web recovered"]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_notification_is_retried_after_the_backoff() {
+        let (worker, received) = worker("retried", Recorder::new(&["web down"; 2], &[]));
+        let _tx = run(worker, vec![down("web")], 100).await;
+        assert_eq!(*received.lock().unwrap(), ["web down"]);
+        time::sleep(Duration::from_millis(450)).await;
+        assert_eq!(*received.lock().unwrap(), ["web down"; 3]);
+    }
+
+    #[tokio::test]
+    async fn retried_notification_holds_back_only_its_service() {
+        let (worker, received) = worker("ordering", Recorder::new(&["web down"], &[]));
+        run(worker, vec![down("web"), recovered("web"), down("db")], 600).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["web down", "db down", "web down", "web recovered"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_notification_is_dropped() {
+        let (worker, received) = worker("rejected", Recorder::new(&[], &["web down"]));
+        run(worker, vec![down("web"), recovered("web")], 100).await;
+        assert_eq!(*received.lock().unwrap(), ["web down", "web recovered"]);
+    }
+
+    #[tokio::test]
+    async fn expired_notification_is_dropped() {
+        let (mut worker, received) = worker("expired", Recorder::new(&["web down"; 10], &[]));
+        worker.policy = Arc::new(OutboxPolicy {
+            max_age: SignedDuration::from_millis(300),
+            ..(*worker.policy).clone()
+        });
+        run(worker, vec![down("web")], 700).await;
+        assert_eq!(*received.lock().unwrap(), ["web down"; 2]);
+    }
+
+    fn digest(window_ms: u64, rate_limit: Option<usize>) -> Option<Arc<DigestPolicy>> {
+        Some(Arc::new(DigestPolicy {
+            window: Duration::from_millis(window_ms),
+            rate_limit,
+            rate_period: Duration::from_millis(400),
+        }))
+    }
+
+    #[tokio::test]
+    async fn notifications_of_the_window_are_merged() {
+        let (mut worker, received) = worker("merged", Recorder::default());
+        worker.digest = digest(100, None);
+        let tx = run(worker, vec![down("web"), down("db")], 50).await;
+        tx.send(recovered("api")).unwrap();
+        time::sleep(Duration::from_millis(200)).await;
+        tx.send(down("cache")).unwrap();
+        time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["web down, db down, api recovered", "cache down"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limit_merges_the_notifications_held() {
+        let (mut worker, received) = worker("rate-limited", Recorder::default());
+        worker.digest = digest(50, Some(1));
+        worker.limiter = RateLimiter::new(worker.digest.as_deref());
+        let tx = run(worker, vec![down("web")], 150).await;
+        tx.send(down("db")).unwrap();
+        tx.send(down("api")).unwrap();
+        time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(*received.lock().unwrap(), ["web down"]);
+        time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(*received.lock().unwrap(), ["web down", "db down, api down"]);
+    }
+
+    #[tokio::test]
+    async fn digest_leaves_out_the_service_waiting_for_a_retry() {
+        let (mut worker, received) = worker("digest-retry", Recorder::new(&["web down"], &[]));
+        worker.digest = digest(50, None);
+        let tx = run(worker, vec![down("web")], 100).await;
+        tx.send(recovered("web")).unwrap();
+        tx.send(down("db")).unwrap();
+        time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["web down", "db down", "web down, web recovered"]
+        );
+    }
+}
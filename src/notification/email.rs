@@ -1,28 +1,120 @@
 use std::{fs, io::Read, path::PathBuf};
 
+use async_trait::async_trait;
 use lettre::{
-    Address, Message, SmtpTransport, Transport,
-    message::{Mailbox, MessageBuilder, header::ContentType},
-    transport::smtp::authentication::Credentials,
+    Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, MessageBuilder, MultiPart},
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Certificate, Tls, TlsParameters},
+        extension::ClientId,
+    },
 };
-use tracing::{Level, event, span};
 use yaml_rust2::Yaml;
 
 use crate::{
-    checker::structs::CheckerResult,
-    notification::{NotificationType, Notifier, error::NotificationParseError},
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields,
+        template::Template,
+    },
     parser::ConfigKey,
 };
 
+const DEFAULT_BODY: &str = r#"
+<!DOCTYPE html>
+<html>
+  <head>
+    <style>
+      p {margin: .5em 0 .5em 0; font-size: 16px; white-space: pre-line;}
+    </style>
+  </head>
+  <body>
+    <h1>Toktok!</h1>
+    <p>{{intro}}</p>
+    <p>Service: {{service}}</p>
+    <p>Reported status: {{status}}</p>
+    <p>Message: {{message}}</p>
+  </body>
+</html>
+"#;
+
+const DEFAULT_TEXT: &str = "Toktok!
+
+{{intro}}
+
+Service: {{service}}
+Reported status: {{status}}
+Message: {{message}}
+";
+
 struct MailCredentials {
     user: String,
     pass: String,
 }
 
+/// How the connection with the SMTP server is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SmtpTls {
+    None,
+    StartTls,
+    Implicit,
+}
+impl SmtpTls {
+    fn default_port(&self) -> u16 {
+        match self {
+            SmtpTls::None => 25,
+            SmtpTls::StartTls => 587,
+            SmtpTls::Implicit => 465,
+        }
+    }
+}
+
+/// The optional connection settings of the `mailer` section.
+/// Without `tls`, the connection uses implicit TLS when there are credentials, none otherwise.
+struct SmtpSettings {
+    port: Option<u16>,
+    tls: Option<SmtpTls>,
+    ca_file: Option<String>,
+    helo_name: Option<String>,
+}
+impl TryFrom<&Yaml> for SmtpSettings {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let tls = match fields::optional_str(data, ConfigKey::Tls)?.as_deref() {
+            Some("none") => Some(SmtpTls::None),
+            Some("starttls") => Some(SmtpTls::StartTls),
+            Some("implicit") => Some(SmtpTls::Implicit),
+            Some(_) => {
+                return Err(NotificationParseError::InvalidFormat(
+                    ConfigKey::Tls,
+                    "one of none, starttls or implicit".to_string(),
+                ));
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            port: fields::optional_int(data, ConfigKey::SmtpPort, 1..=65535)?
+                .map(|port| port as u16),
+            tls,
+            ca_file: fields::optional_str(data, ConfigKey::TlsCaFile)?,
+            helo_name: fields::optional_str(data, ConfigKey::HeloName)?,
+        })
+    }
+}
+
+/// Sends the notification by email, as a HTML body with a plain text alternative.
+/// The subject and both bodies can be customized with template files.
 #[derive(Debug, Clone)]
 pub struct MailNotifier {
-    mailer: SmtpTransport,
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
     base_msg_builder: MessageBuilder,
+    subject: Option<Template>,
+    body: Template,
+    text: Template,
 }
 impl MailNotifier {
     fn try_new(
@@ -30,60 +122,49 @@ impl MailNotifier {
         from: &str,
         to: &str,
         credentials_path: Option<&str>,
+        settings: SmtpSettings,
     ) -> Result<MailNotifierBuilder, NotificationParseError> {
-        MailNotifierBuilder::new(domain, from, to, credentials_path)
+        MailNotifierBuilder::new(domain, from, to, credentials_path, settings)
     }
 }
 
+#[async_trait]
 impl Notifier for MailNotifier {
-    fn notify(&self, exec_result: &CheckerResult) {
-        let span = span!(Level::INFO, "MailNotifier::notify");
-        let _enter = span.enter();
-
-        let body = format!(
-            r#"
-<!DOCTYPE html>
-<html>
-  <head>
-    <style>
-      p {}
-    </style>
-  </head>
-  <body>
-    <h1>Toktok!</h1>
-    <p>Hello, a service reported with an unexpected status through the last verification</p>
-    <p>Service: {}</p>
-    <p>Reported status: {}</p>
-    <p>Message: {}</p>
-  </body>
-</html>
-"#,
-            "{margin: .5em 0 .5em 0; font-size: 16px;}",
-            exec_result.service_name,
-            exec_result.status,
-            exec_result.message
-        );
-
-        let email = self.base_msg_builder.clone().body(body);
-        if let Err(e) = email {
-            event!(Level::ERROR, error = %e, "Error building the email message");
-            return;
-        }
-
-        match self.mailer.send(&email.unwrap()) {
-            Ok(_) => event!(Level::INFO, "Email notification sent successfully"),
-            Err(err) => event!(
-                Level::ERROR,
-                error = %err,
-                "Error sending the Email notification"
-            ),
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let subject = match &self.subject {
+            Some(subject) => subject
+                .render(notification)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            None if notification.is_recovery() => "Toktok Service Recovered!".to_string(),
+            None if notification.digested().is_some() => "Toktok Services Digest!".to_string(),
+            None => "Toktok Service Alert!".to_string(),
         };
+
+        let email = self
+            .base_msg_builder
+            .clone()
+            .subject(subject)
+            .multipart(MultiPart::alternative_plain_html(
+                self.text.render(notification),
+                self.body.render_html(notification),
+            ))
+            .map_err(|e| NotificationError::InvalidMessage(e.to_string()))?;
+
+        self.mailer.send(email).await?;
+        Ok(())
     }
 }
 
 struct MailNotifierBuilder {
-    smtp_transport: SmtpTransport,
+    smtp_transport: AsyncSmtpTransport<Tokio1Executor>,
     msg_builder: MessageBuilder,
+    subject: Option<Template>,
+    body: Template,
+    text: Template,
 }
 impl MailNotifierBuilder {
     fn new(
@@ -91,14 +172,18 @@ impl MailNotifierBuilder {
         from: &str,
         to: &str,
         credentials_path: Option<&str>,
+        settings: SmtpSettings,
     ) -> Result<Self, NotificationParseError> {
         let credentials = MailNotifierBuilder::credentials(credentials_path)?;
-        let smtp_transport = MailNotifierBuilder::smtp_transport(credentials, domain)?;
+        let smtp_transport = MailNotifierBuilder::smtp_transport(credentials, domain, settings)?;
         let msg_builder = MailNotifierBuilder::msg_builder(from, to)?;
 
         Ok(Self {
             smtp_transport,
             msg_builder,
+            subject: None,
+            body: Template::new(DEFAULT_BODY),
+            text: Template::new(DEFAULT_TEXT),
         })
     }
 
@@ -148,20 +233,66 @@ impl MailNotifierBuilder {
     fn smtp_transport(
         credentials: Option<MailCredentials>,
         domain: &str,
-    ) -> Result<SmtpTransport, NotificationParseError> {
+        settings: SmtpSettings,
+    ) -> Result<AsyncSmtpTransport<Tokio1Executor>, NotificationParseError> {
+        let tls = settings.tls.unwrap_or(if credentials.is_some() {
+            SmtpTls::Implicit
+        } else {
+            SmtpTls::None
+        });
+        let mut transport_builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(domain)
+            .port(settings.port.unwrap_or(tls.default_port()));
+
+        if tls == SmtpTls::None {
+            if settings.ca_file.is_some() {
+                return Err(NotificationParseError::InternalParse(format!(
+                    "Key '{}' cannot be used without TLS",
+                    ConfigKey::TlsCaFile
+                )));
+            }
+        } else {
+            let tls_parameters = MailNotifierBuilder::tls_parameters(domain, settings.ca_file)?;
+            transport_builder = transport_builder.tls(match tls {
+                SmtpTls::StartTls => Tls::Required(tls_parameters),
+                _ => Tls::Wrapper(tls_parameters),
+            });
+        }
+
+        if let Some(helo_name) = settings.helo_name {
+            transport_builder = transport_builder.hello_name(ClientId::Domain(helo_name));
+        }
         if let Some(credentials) = credentials {
-            let transport_builder = SmtpTransport::relay(domain).map_err(|e| {
-                NotificationParseError::InternalBuild(format!(
-                    "The program was unable to build a SMTP relay with the provided domain: {e}"
+            transport_builder =
+                transport_builder.credentials(Credentials::new(credentials.user, credentials.pass));
+        }
+
+        Ok(transport_builder.build())
+    }
+
+    /// The TLS parameters for the SMTP server, trusting the PEM certificate of the
+    /// optional CA file along with the system ones.
+    fn tls_parameters(
+        domain: &str,
+        ca_file: Option<String>,
+    ) -> Result<TlsParameters, NotificationParseError> {
+        let mut tls_builder = TlsParameters::builder(domain.to_string());
+        if let Some(ca_file) = ca_file {
+            let pem = fs::read(&ca_file)
+                .map_err(|e| NotificationParseError::UnableToReadFile(ca_file.clone(), e))?;
+            let certificate = Certificate::from_pem(&pem).map_err(|e| {
+                NotificationParseError::InternalParse(format!(
+                    "The certificate in '{}' is not valid: {e}",
+                    ConfigKey::TlsCaFile
                 ))
             })?;
-
-            Ok(transport_builder
-                .credentials(Credentials::new(credentials.user, credentials.pass))
-                .build())
-        } else {
-            Ok(SmtpTransport::builder_dangerous(domain).build())
+            tls_builder = tls_builder.add_root_certificate(certificate);
         }
+
+        tls_builder.build().map_err(|e| {
+            NotificationParseError::InternalBuild(format!(
+                "The program was unable to build the SMTP TLS parameters for the domain: {e}"
+            ))
+        })
     }
 
     fn msg_builder(from: &str, to: &str) -> Result<MessageBuilder, NotificationParseError> {
@@ -184,11 +315,7 @@ impl MailNotifierBuilder {
             })?,
         );
 
-        Ok(Message::builder()
-            .from(from_box)
-            .to(to_box)
-            .subject("Toktok Service Alert!")
-            .header(ContentType::TEXT_HTML))
+        Ok(Message::builder().from(from_box).to(to_box))
     }
 
     fn cc(mut self, list: Vec<String>) -> Result<Self, NotificationParseError> {
@@ -235,10 +362,31 @@ impl MailNotifierBuilder {
         Ok(self)
     }
 
+    /// Replace the default subject and bodies by the ones of the provided templates.
+    fn templates(
+        mut self,
+        subject: Option<Template>,
+        body: Option<Template>,
+        text: Option<Template>,
+    ) -> Self {
+        self.subject = subject;
+        if let Some(body) = body {
+            self.body = body;
+        }
+        if let Some(text) = text {
+            self.text = text;
+        }
+
+        self
+    }
+
     fn build(self) -> MailNotifier {
         MailNotifier {
             mailer: self.smtp_transport,
             base_msg_builder: self.msg_builder,
+            subject: self.subject,
+            body: self.body,
+            text: self.text,
         }
     }
 }
@@ -282,7 +430,9 @@ impl TryFrom<&Yaml> for MailNotifier {
             _ => None,
         };
 
-        let mut mail_builder = MailNotifier::try_new(smtp_domain, from, to, credentials_path)?;
+        let settings = SmtpSettings::try_from(data)?;
+        let mut mail_builder =
+            MailNotifier::try_new(smtp_domain, from, to, credentials_path, settings)?;
 
         let cc: Option<Vec<String>> = match &data[ConfigKey::MailCc.as_ref()] {
             Yaml::Array(cc_list)
@@ -330,6 +480,12 @@ impl TryFrom<&Yaml> for MailNotifier {
             mail_builder = mail_builder.bcc(bcc)?;
         }
 
+        mail_builder = mail_builder.templates(
+            Template::from_file(data, ConfigKey::SubjectTemplate)?,
+            Template::from_file(data, ConfigKey::BodyTemplate)?,
+            Template::from_file(data, ConfigKey::TextTemplate)?,
+        );
+
         Ok(mail_builder.build())
     }
 }
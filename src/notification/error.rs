@@ -1,5 +1,7 @@
 use std::{fmt::Display, io};
 
+use reqwest::StatusCode;
+
 use crate::{
     notification::NotificationType,
     parser::{ConfigKey, error::ConfigError},
@@ -8,6 +10,7 @@ use crate::{
 #[derive(Debug)]
 pub enum NotificationParseError {
     KeyNotFoundAt(ConfigKey, NotificationType),
+    InvalidType(String, String),
     InvalidFormat(ConfigKey, String),
     InternalParse(String),
     InternalBuild(String),
@@ -23,6 +26,9 @@ impl Display for NotificationParseError {
                     "Key '{key}' is mandatory for a notification of type {c_type}."
                 )
             }
+            NotificationParseError::InvalidType(name, e) => {
+                write!(f, "Invalid type for the notifier '{name}': {e}.")
+            }
             NotificationParseError::InvalidFormat(key, format) => {
                 write!(f, "Invalid format for '{key}'. Expected: {format}")
             }
@@ -40,3 +46,132 @@ impl Display for NotificationParseError {
     }
 }
 impl ConfigError for NotificationParseError {}
+
+/// The failure of a notifier while sending a notification.
+#[derive(Debug)]
+pub enum NotificationError {
+    Http(reqwest::Error),
+    Smtp(lettre::transport::smtp::Error),
+    Io(io::Error),
+    InvalidMessage(String),
+    /// The notifier can't send this notification, like a service without a routing key.
+    Misconfigured(String),
+    Failed(String),
+}
+impl NotificationError {
+    /// Whether a new attempt may succeed, like after a network error, a server error,
+    /// a timeout or a rate limit. A rejected request or a config error will fail again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            NotificationError::Http(e) => match e.status() {
+                Some(status) => {
+                    status.is_server_error()
+                        || status == StatusCode::REQUEST_TIMEOUT
+                        || status == StatusCode::TOO_MANY_REQUESTS
+                }
+                None => !e.is_builder(),
+            },
+            NotificationError::Smtp(e) => !e.is_permanent() && !e.is_client(),
+            NotificationError::Io(e) => !matches!(
+                e.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+            ),
+            NotificationError::InvalidMessage(_) | NotificationError::Misconfigured(_) => false,
+            NotificationError::Failed(_) => true,
+        }
+    }
+}
+impl Display for NotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationError::Http(e) => write!(f, "HTTP request failed: {e}"),
+            NotificationError::Smtp(e) => write!(f, "SMTP transaction failed: {e}"),
+            NotificationError::Io(e) => write!(f, "I/O operation failed: {e}"),
+            NotificationError::InvalidMessage(e) => write!(f, "Unable to build the message: {e}"),
+            NotificationError::Misconfigured(e) => write!(f, "{e}"),
+            NotificationError::Failed(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for NotificationError {}
+impl From<reqwest::Error> for NotificationError {
+    fn from(e: reqwest::Error) -> Self {
+        NotificationError::Http(e)
+    }
+}
+impl From<lettre::transport::smtp::Error> for NotificationError {
+    fn from(e: lettre::transport::smtp::Error) -> Self {
+        NotificationError::Smtp(e)
+    }
+}
+impl From<io::Error> for NotificationError {
+    fn from(e: io::Error) -> Self {
+        NotificationError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use super::*;
+    use crate::notification::{http, testing::Listener};
+
+    #[tokio::test]
+    async fn http_status_is_retryable() {
+        let cases = [
+            (500, true),
+            (502, true),
+            (503, true),
+            (408, true),
+            (429, true),
+            (400, false),
+            (401, false),
+            (403, false),
+            (404, false),
+            (413, false),
+        ];
+        for (status, retryable) in cases {
+            let listener = Listener::start(status).await;
+            let err = http::send(Client::new().post(&listener.url))
+                .await
+                .unwrap_err();
+            assert_eq!(err.is_retryable(), retryable, "retry of a {status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn http_failure_without_status_is_retryable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let refused = http::send(Client::new().post(&url)).await.unwrap_err();
+        assert!(refused.is_retryable());
+
+        let invalid_header = Client::new().post(&url).header("invalid header", "value");
+        let invalid_request = http::send(invalid_header).await.unwrap_err();
+        assert!(!invalid_request.is_retryable());
+    }
+
+    #[test]
+    fn other_failures_are_retryable() {
+        let cases = [
+            (NotificationError::Io(io::ErrorKind::NotFound.into()), false),
+            (
+                NotificationError::Io(io::ErrorKind::PermissionDenied.into()),
+                false,
+            ),
+            (
+                NotificationError::Io(io::ErrorKind::ConnectionRefused.into()),
+                true,
+            ),
+            (NotificationError::Io(io::ErrorKind::TimedOut.into()), true),
+            (NotificationError::InvalidMessage(String::new()), false),
+            (NotificationError::Misconfigured(String::new()), false),
+            (NotificationError::Failed(String::new()), true),
+        ];
+        for (err, retryable) in cases {
+            assert_eq!(err.is_retryable(), retryable, "retry of {err:?}");
+        }
+    }
+}
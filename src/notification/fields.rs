@@ -0,0 +1,175 @@
+use std::{fs, io::Read, ops::RangeInclusive, time::Duration};
+
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{NotificationType, error::NotificationParseError},
+    parser::ConfigKey,
+};
+
+/// Read a mandatory, non empty, string key of a notification section.
+pub fn required_str(
+    data: &Yaml,
+    key: ConfigKey,
+    notification_type: NotificationType,
+) -> Result<String, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::String(value) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+        _ => Err(NotificationParseError::KeyNotFoundAt(
+            key,
+            notification_type,
+        )),
+    }
+}
+
+/// Read an optional string key of a notification section.
+pub fn optional_str(data: &Yaml, key: ConfigKey) -> Result<Option<String>, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::String(value) if !value.trim().is_empty() => Ok(Some(value.trim().to_string())),
+        Yaml::BadValue => Ok(None),
+        _ => Err(NotificationParseError::InvalidFormat(
+            key,
+            "a non empty string".to_string(),
+        )),
+    }
+}
+
+/// Read a mandatory, non empty, list of values of a notification section.
+/// Numbers are accepted too, as ids like chat ids are usually numeric.
+pub fn required_list(
+    data: &Yaml,
+    key: ConfigKey,
+    notification_type: NotificationType,
+) -> Result<Vec<String>, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::Array(list) if !list.is_empty() => list
+            .iter()
+            .map(|value| match value {
+                Yaml::String(value) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+                Yaml::Integer(value) => Ok(value.to_string()),
+                _ => Err(NotificationParseError::InternalParse(format!(
+                    "Key '{key}' must contain only strings or numbers"
+                ))),
+            })
+            .collect(),
+        Yaml::BadValue => Err(NotificationParseError::KeyNotFoundAt(
+            key,
+            notification_type,
+        )),
+        _ => Err(NotificationParseError::InvalidFormat(
+            key,
+            "a non empty YAML Array".to_string(),
+        )),
+    }
+}
+
+/// Read an optional integer key of a notification section, which must be inside `range`.
+pub fn optional_int(
+    data: &Yaml,
+    key: ConfigKey,
+    range: RangeInclusive<i64>,
+) -> Result<Option<i64>, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::Integer(value) if range.contains(value) => Ok(Some(*value)),
+        Yaml::BadValue => Ok(None),
+        _ => Err(NotificationParseError::InvalidFormat(
+            key,
+            format!("a number between {} and {}", range.start(), range.end()),
+        )),
+    }
+}
+
+/// Read an optional integer key which must be greater than zero, like an amount of seconds.
+pub fn optional_positive(
+    data: &Yaml,
+    key: ConfigKey,
+) -> Result<Option<u64>, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::Integer(value) if *value > 0 => Ok(Some(*value as u64)),
+        Yaml::BadValue => Ok(None),
+        _ => Err(NotificationParseError::InvalidFormat(
+            key,
+            "a number greater than zero".to_string(),
+        )),
+    }
+}
+
+/// Read an optional list of strings of a notification section.
+pub fn optional_list(data: &Yaml, key: ConfigKey) -> Result<Vec<String>, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::Array(list) if list.iter().all(|value| value.as_str().is_some()) => Ok(list
+            .iter()
+            .map(|value| value.as_str().unwrap().to_string())
+            .collect()),
+        Yaml::BadValue => Ok(vec![]),
+        _ => Err(NotificationParseError::InvalidFormat(
+            key,
+            "a YAML Array of strings".to_string(),
+        )),
+    }
+}
+
+/// Read the secret stored in the file which path is at `key`, like an API token.
+pub fn secret_file(
+    data: &Yaml,
+    key: ConfigKey,
+    notification_type: NotificationType,
+) -> Result<String, NotificationParseError> {
+    let path = required_str(data, key, notification_type)?;
+    read_secret(&path, key)
+}
+
+/// Read the secret stored in the file at `path`, the value of `key`.
+/// Only the first line of the file is used, without surrounding whitespaces.
+pub fn read_secret(path: &str, key: ConfigKey) -> Result<String, NotificationParseError> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| NotificationParseError::UnableToOpenFile(path.to_string(), e))?;
+
+    let mut buff = String::new();
+    file.read_to_string(&mut buff)
+        .map_err(|e| NotificationParseError::UnableToReadFile(path.to_string(), e))?;
+
+    match buff.lines().next().map(str::trim) {
+        Some(secret) if !secret.is_empty() => Ok(secret.to_string()),
+        _ => Err(NotificationParseError::InternalParse(format!(
+            "The file in '{key}' cannot be empty"
+        ))),
+    }
+}
+
+/// Read the whole content of the optional file which path is at `key`, like a template.
+pub fn optional_file(
+    data: &Yaml,
+    key: ConfigKey,
+) -> Result<Option<String>, NotificationParseError> {
+    let Some(path) = optional_str(data, key)? else {
+        return Ok(None);
+    };
+
+    let mut file = fs::File::open(&path)
+        .map_err(|e| NotificationParseError::UnableToOpenFile(path.clone(), e))?;
+
+    let mut buff = String::new();
+    file.read_to_string(&mut buff)
+        .map_err(|e| NotificationParseError::UnableToReadFile(path.clone(), e))?;
+
+    if buff.trim().is_empty() {
+        return Err(NotificationParseError::InternalParse(format!(
+            "The file in '{key}' cannot be empty"
+        )));
+    }
+
+    Ok(Some(buff))
+}
+
+/// Read the optional `timeout` key of a notification section, in seconds.
+pub fn timeout(data: &Yaml, default: Duration) -> Result<Duration, NotificationParseError> {
+    match &data[ConfigKey::Timeout.as_ref()] {
+        Yaml::Integer(time) if *time > 0 => Ok(Duration::from_secs(*time as u64)),
+        Yaml::BadValue => Ok(default),
+        _ => Err(NotificationParseError::InvalidFormat(
+            ConfigKey::Timeout,
+            "a number greater than zero".to_string(),
+        )),
+    }
+}
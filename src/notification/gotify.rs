@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_PRIORITY: i64 = 8;
+
+/// Pushes the notification as a message of a Gotify application.
+#[derive(Debug, Clone)]
+pub struct GotifyNotifier {
+    client: Client,
+    message_url: Url,
+    app_token: String,
+    priority: i64,
+}
+
+#[async_trait]
+impl Notifier for GotifyNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let request = self
+            .client
+            .post(self.message_url.clone())
+            .header("X-Gotify-Key", &self.app_token)
+            .json(&json!({
+                "title": notification.title(),
+                "message": notification.result.message,
+                "priority": self.priority,
+            }));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for GotifyNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let mut message_url = http::url(data, ConfigKey::ServerUrl, NotificationType::Gotify)?;
+        if let Ok(mut segments) = message_url.path_segments_mut() {
+            segments.pop_if_empty().push("message");
+        }
+
+        Ok(GotifyNotifier {
+            client: http::client(data)?,
+            message_url,
+            app_token: fields::secret_file(
+                data,
+                ConfigKey::AppTokenFile,
+                NotificationType::Gotify,
+            )?,
+            priority: fields::optional_int(data, ConfigKey::Priority, 0..=10)?
+                .unwrap_or(DEFAULT_PRIORITY),
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::{str::FromStr, time::Duration};
+
+use reqwest::{
+    Client, RequestBuilder, Url,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        NotificationType,
+        error::{NotificationError, NotificationParseError},
+        fields,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Build the client shared by the HTTP based notifiers,
+/// using the optional `timeout` key of the notification section.
+pub fn client(data: &Yaml) -> Result<Client, NotificationParseError> {
+    let timeout = fields::timeout(data, DEFAULT_TIMEOUT)?;
+
+    Client::builder().timeout(timeout).build().map_err(|e| {
+        NotificationParseError::InternalBuild(format!(
+            "The program was unable to build the HTTP client: {e}"
+        ))
+    })
+}
+
+/// Parse the mandatory URL at `key` of a notification section.
+pub fn url(
+    data: &Yaml,
+    key: ConfigKey,
+    notification_type: NotificationType,
+) -> Result<Url, NotificationParseError> {
+    match &data[key.as_ref()] {
+        Yaml::String(url) => Url::parse(url).map_err(|e| {
+            NotificationParseError::InternalParse(format!(
+                "The {notification_type} '{key}' is not a valid URL: {e}"
+            ))
+        }),
+        _ => Err(NotificationParseError::KeyNotFoundAt(
+            key,
+            notification_type,
+        )),
+    }
+}
+
+/// Parse the optional `headers` map of a notification section.
+pub fn headers(data: &Yaml) -> Result<HeaderMap, NotificationParseError> {
+    let mut header_map = HeaderMap::new();
+    match &data[ConfigKey::Headers.as_ref()] {
+        Yaml::Hash(headers) => {
+            for (key, value) in headers.iter() {
+                let (Yaml::String(key), Yaml::String(value)) = (key, value) else {
+                    return Err(NotificationParseError::InvalidFormat(
+                        ConfigKey::Headers,
+                        "a map of header names to string values".to_string(),
+                    ));
+                };
+                let header_name = HeaderName::from_str(key).map_err(|e| {
+                    NotificationParseError::InternalParse(format!(
+                        "Invalid header name '{key}': {e}"
+                    ))
+                })?;
+                let header_value = HeaderValue::from_str(value).map_err(|e| {
+                    NotificationParseError::InternalParse(format!(
+                        "Invalid value for header '{key}': {e}"
+                    ))
+                })?;
+                header_map.insert(header_name, header_value);
+            }
+        }
+        Yaml::BadValue => {}
+        _ => {
+            return Err(NotificationParseError::InvalidFormat(
+                ConfigKey::Headers,
+                "a map of header names to string values".to_string(),
+            ));
+        }
+    }
+
+    Ok(header_map)
+}
+
+/// Send the request of a HTTP based notifier, a non success status is reported as an error.
+/// The URL is removed from the error, as webhook and bot URLs usually carry a secret.
+pub async fn send(request: RequestBuilder) -> Result<(), NotificationError> {
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| NotificationError::Http(e.without_url()))?;
+    Ok(())
+}
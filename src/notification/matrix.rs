@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+        template::Template,
+    },
+    parser::ConfigKey,
+};
+
+/// Posts the notification to a Matrix room through the client-server API.
+#[derive(Debug, Clone)]
+pub struct MatrixNotifier {
+    client: Client,
+    homeserver_url: Url,
+    room_id: String,
+    access_token: String,
+}
+
+impl MatrixNotifier {
+    /// The URL to send a message event, the transaction id must be unique for each message.
+    /// It's made of the notification, so the server ignores a new attempt of a message
+    /// it already received.
+    fn send_url(&self, notification: &Notification) -> Url {
+        let txn_id = format!(
+            "toktok-{}-{}{}-{}",
+            notification.result.service_name,
+            notification.kind,
+            notification.escalation_tier().unwrap_or_default(),
+            notification.result.checked_at.timestamp().as_nanosecond()
+        );
+        let mut url = self.homeserver_url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().extend([
+                "_matrix",
+                "client",
+                "v3",
+                "rooms",
+                &self.room_id,
+                "send",
+                "m.room.message",
+                &txn_id,
+            ]);
+        }
+        url
+    }
+}
+
+#[async_trait]
+impl Notifier for MatrixNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let exec_result = &notification.result;
+        let body = format!(
+            "Toktok!\n{}\nService: {}\nReported status: {}\nMessage: {}",
+            notification.intro(),
+            exec_result.service_name,
+            exec_result.status,
+            exec_result.message
+        );
+        let formatted_body = format!(
+            "<h1>Toktok!</h1><p>{}</p><p>Service: {}</p>\
+             <p>Reported status: {}</p><p>Message: {}</p>",
+            Template::escape_html(&notification.intro()),
+            Template::escape_html(&exec_result.service_name),
+            exec_result.status,
+            Template::escape_html(&exec_result.message)
+        );
+
+        let request = self
+            .client
+            .put(self.send_url(notification))
+            .bearer_auth(&self.access_token)
+            .json(&json!({
+                "msgtype": "m.text",
+                "body": body,
+                "format": "org.matrix.custom.html",
+                "formatted_body": formatted_body,
+            }));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for MatrixNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        Ok(MatrixNotifier {
+            client: http::client(data)?,
+            homeserver_url: http::url(data, ConfigKey::HomeserverUrl, NotificationType::Matrix)?,
+            room_id: fields::required_str(data, ConfigKey::RoomId, NotificationType::Matrix)?,
+            access_token: fields::secret_file(
+                data,
+                ConfigKey::AccessTokenFile,
+                NotificationType::Matrix,
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::{
+        NotificationKind,
+        testing::{notification, secret_file, section},
+    };
+
+    #[test]
+    fn transaction_id_is_the_same_for_each_attempt() {
+        let token = secret_file("matrix.token", "syt_token");
+        let notifier = MatrixNotifier::try_from(&section(&format!(
+            "homeserver_url: https://matrix.test\nroom_id: '!ops:matrix.test'\n\
+             access_token_file: {token}"
+        )))
+        .unwrap();
+        let down = notification("db main", "Refused");
+        assert_eq!(notifier.send_url(&down), notifier.send_url(&down));
+        assert_eq!(
+            notifier.send_url(&down).as_str(),
+            "https://matrix.test/_matrix/client/v3/rooms/!ops:matrix.test/send/m.room.message/\
+             toktok-db%20main-down0-1767323045000000000"
+        );
+
+        let escalated = |tier| Notification {
+            kind: NotificationKind::Escalated {
+                tier,
+                down_since: down.result.checked_at.clone(),
+            },
+            ..down.clone()
+        };
+        assert_ne!(
+            notifier.send_url(&escalated(1)),
+            notifier.send_url(&escalated(2))
+        );
+    }
+}
@@ -1,22 +1,535 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use crate::checker::structs::CheckerResult;
+use async_trait::async_trait;
+use jiff::{SignedDuration, Zoned};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::{Instrument, Level, event, span};
+use yaml_rust2::Yaml;
 
+use crate::{
+    checker::structs::{CheckerResult, CheckerStatus},
+    notification::{
+        command::CommandNotifier,
+        discord::DiscordNotifier,
+        email::MailNotifier,
+        error::{NotificationError, NotificationParseError},
+        gotify::GotifyNotifier,
+        matrix::MatrixNotifier,
+        ntfy::NtfyNotifier,
+        opsgenie::OpsgenieNotifier,
+        pagerduty::PagerDutyNotifier,
+        pushover::PushoverNotifier,
+        slack::SlackNotifier,
+        sms::SmsNotifier,
+        syslog::SyslogNotifier,
+        teams::TeamsNotifier,
+        telegram::TelegramNotifier,
+        webhook::WebhookNotifier,
+    },
+};
+
+/// How many notifications a digest lists, so its message fits the limits of the chat APIs,
+/// the smallest being the 1024 characters of Pushover.
+const DIGEST_MAX_ENTRIES: usize = 10;
+const DIGEST_MAX_LINE_LENGTH: usize = 80;
+
+pub mod command;
+pub mod digest;
+pub mod discord;
+pub mod dispatcher;
 pub mod email;
 pub mod error;
+pub mod fields;
+pub mod gotify;
+pub mod http;
+pub mod matrix;
+pub mod ntfy;
+pub mod opsgenie;
+pub mod outbox;
+pub mod pagerduty;
+pub mod pushover;
+pub mod slack;
+pub mod sms;
+pub mod syslog;
+pub mod teams;
+pub mod telegram;
+pub mod template;
+#[cfg(test)]
+mod testing;
+pub mod webhook;
+
+/// Sends a notification through a channel, like an email or a chat message.
+/// A failed delivery is returned to the caller, which is responsible for reporting it.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError>;
+
+    /// Check at config load the notifier is able to alert the `routed` services,
+    /// `services` being all the services of the configuration.
+    fn check_services(&self, _routed: &[String], _services: &[String]) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A notifier instance, named by its key inside the `notification` section.
+pub struct NamedNotifier {
+    pub name: String,
+    pub notifier: Box<dyn Notifier>,
+}
+impl NamedNotifier {
+    pub fn new(name: String, notifier: Box<dyn Notifier>) -> Self {
+        Self { name, notifier }
+    }
+
+    /// Send the notification, logging the result along with the notifier name.
+    pub async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let span = span!(
+            Level::INFO,
+            "NamedNotifier::notify",
+            notifier = self.name,
+            service = notification.result.service_name,
+            event = %notification.kind
+        );
+        let result = self
+            .notifier
+            .notify(notification)
+            .instrument(span.clone())
+            .await;
 
-pub trait Notifier {
-    fn notify(&self, exec_result: &CheckerResult);
+        let _enter = span.enter();
+        match &result {
+            Ok(_) => event!(Level::INFO, "Notification sent successfully"),
+            Err(err) => event!(Level::ERROR, error = %err, "Error sending the notification"),
+        };
+        result
+    }
 }
 
 #[derive(Debug)]
 pub enum NotificationType {
     Email,
+    Webhook,
+    Slack,
+    Discord,
+    Teams,
+    Telegram,
+    PagerDuty,
+    Opsgenie,
+    Command,
+    Ntfy,
+    Gotify,
+    Pushover,
+    Matrix,
+    Sms,
+    Syslog,
 }
 impl Display for NotificationType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             NotificationType::Email => write!(f, "email"),
+            NotificationType::Webhook => write!(f, "webhook"),
+            NotificationType::Slack => write!(f, "slack"),
+            NotificationType::Discord => write!(f, "discord"),
+            NotificationType::Teams => write!(f, "teams"),
+            NotificationType::Telegram => write!(f, "telegram"),
+            NotificationType::PagerDuty => write!(f, "pagerduty"),
+            NotificationType::Opsgenie => write!(f, "opsgenie"),
+            NotificationType::Command => write!(f, "command"),
+            NotificationType::Ntfy => write!(f, "ntfy"),
+            NotificationType::Gotify => write!(f, "gotify"),
+            NotificationType::Pushover => write!(f, "pushover"),
+            NotificationType::Matrix => write!(f, "matrix"),
+            NotificationType::Sms => write!(f, "sms"),
+            NotificationType::Syslog => write!(f, "syslog"),
+        }
+    }
+}
+impl FromStr for NotificationType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mailer" | "email" => Ok(NotificationType::Email),
+            "webhook" => Ok(NotificationType::Webhook),
+            "slack" => Ok(NotificationType::Slack),
+            "discord" => Ok(NotificationType::Discord),
+            "teams" => Ok(NotificationType::Teams),
+            "telegram" => Ok(NotificationType::Telegram),
+            "pagerduty" => Ok(NotificationType::PagerDuty),
+            "opsgenie" => Ok(NotificationType::Opsgenie),
+            "command" => Ok(NotificationType::Command),
+            "ntfy" => Ok(NotificationType::Ntfy),
+            "gotify" => Ok(NotificationType::Gotify),
+            "pushover" => Ok(NotificationType::Pushover),
+            "matrix" => Ok(NotificationType::Matrix),
+            "sms" => Ok(NotificationType::Sms),
+            "syslog" => Ok(NotificationType::Syslog),
+            _ => Err(format!("Is not a valid type: {s}")),
+        }
+    }
+}
+impl NotificationType {
+    /// Build a notifier of this type from its section of the config file.
+    pub fn notifier(&self, data: &Yaml) -> Result<Box<dyn Notifier>, NotificationParseError> {
+        Ok(match self {
+            NotificationType::Email => Box::new(MailNotifier::try_from(data)?),
+            NotificationType::Webhook => Box::new(WebhookNotifier::try_from(data)?),
+            NotificationType::Slack => Box::new(SlackNotifier::try_from(data)?),
+            NotificationType::Discord => Box::new(DiscordNotifier::try_from(data)?),
+            NotificationType::Teams => Box::new(TeamsNotifier::try_from(data)?),
+            NotificationType::Telegram => Box::new(TelegramNotifier::try_from(data)?),
+            NotificationType::PagerDuty => Box::new(PagerDutyNotifier::try_from(data)?),
+            NotificationType::Opsgenie => Box::new(OpsgenieNotifier::try_from(data)?),
+            NotificationType::Command => Box::new(CommandNotifier::try_from(data)?),
+            NotificationType::Ntfy => Box::new(NtfyNotifier::try_from(data)?),
+            NotificationType::Gotify => Box::new(GotifyNotifier::try_from(data)?),
+            NotificationType::Pushover => Box::new(PushoverNotifier::try_from(data)?),
+            NotificationType::Matrix => Box::new(MatrixNotifier::try_from(data)?),
+            NotificationType::Sms => Box::new(SmsNotifier::try_from(data)?),
+            NotificationType::Syslog => Box::new(SyslogNotifier::try_from(data)?),
+        })
+    }
+}
+
+/// The state transition of a service which is notified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NotificationKind {
+    /// The service failure has been confirmed.
+    Down,
+    /// The service is still down since `down_since` and reached the escalation `tier`,
+    /// starting at 1 for the first tier after the notifiers of the service.
+    Escalated { tier: usize, down_since: Zoned },
+    /// The service is back up after being down since `down_since`.
+    Recovered { down_since: Zoned },
+    /// Several notifications of a notifier merged in a single message,
+    /// sent instead of them during a storm when the `digest` section is defined.
+    Digest { notifications: Vec<Notification> },
+}
+impl Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationKind::Down => write!(f, "down"),
+            NotificationKind::Escalated { .. } => write!(f, "escalated"),
+            NotificationKind::Recovered { .. } => write!(f, "recovered"),
+            NotificationKind::Digest { .. } => write!(f, "digest"),
+        }
+    }
+}
+
+/// What is sent to the notifiers, the `result` is the check which triggered the transition.
+/// The `tags` and `notifiers` are the ones defined in the service config,
+/// without `notifiers` the notification is sent to all of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub result: CheckerResult,
+    pub kind: NotificationKind,
+    pub tags: Vec<String>,
+    pub notifiers: Option<Vec<String>>,
+}
+impl Notification {
+    pub fn new(
+        result: CheckerResult,
+        kind: NotificationKind,
+        tags: Vec<String>,
+        notifiers: Option<Vec<String>>,
+    ) -> Self {
+        Self {
+            result,
+            kind,
+            tags,
+            notifiers,
+        }
+    }
+
+    /// Merge the `notifications`, in their order, into a digest.
+    /// Its result lists the first ones followed by how many more there are,
+    /// with the worst status among them, an error being worse than a timeout.
+    pub fn digest(notifications: Vec<Notification>) -> Self {
+        let mut service_names: Vec<String> = vec![];
+        let mut tags: Vec<String> = vec![];
+        for notification in &notifications {
+            let service_name = &notification.result.service_name;
+            if !service_names.contains(service_name) {
+                service_names.push(service_name.clone());
+            }
+            for tag in &notification.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+
+        let result = CheckerResult {
+            service_name: Notification::capped(service_names, ", "),
+            status: notifications
+                .iter()
+                .map(|notification| notification.result.status)
+                .max_by_key(|status| match status {
+                    CheckerStatus::Success => 0,
+                    CheckerStatus::Timeout => 1,
+                    CheckerStatus::Error => 2,
+                })
+                .unwrap_or(CheckerStatus::Success),
+            message: Notification::capped(
+                notifications
+                    .iter()
+                    .map(|notification| {
+                        let line =
+                            format!("{}: {}", notification.title(), notification.result.message);
+                        match line.char_indices().nth(DIGEST_MAX_LINE_LENGTH - 3) {
+                            Some((end, _)) if line.chars().count() > DIGEST_MAX_LINE_LENGTH => {
+                                format!("{}...", &line[..end])
+                            }
+                            _ => line,
+                        }
+                    })
+                    .collect(),
+                "\n",
+            ),
+            checked_at: notifications
+                .last()
+                .map(|notification| notification.result.checked_at.clone())
+                .unwrap_or_else(Zoned::now),
+        };
+
+        Self::new(
+            result,
+            NotificationKind::Digest { notifications },
+            tags,
+            None,
+        )
+    }
+
+    /// Join the first `items`, followed by how many more there are.
+    fn capped(mut items: Vec<String>, separator: &str) -> String {
+        if items.len() > DIGEST_MAX_ENTRIES {
+            let more = items.len() - DIGEST_MAX_ENTRIES;
+            items.truncate(DIGEST_MAX_ENTRIES);
+            items.push(format!("and {more} more"));
+        }
+        items.join(separator)
+    }
+
+    /// Whether the notification must be sent by the notifier named `name`.
+    pub fn is_routed_to(&self, name: &str) -> bool {
+        self.notifiers
+            .as_ref()
+            .is_none_or(|notifiers| notifiers.iter().any(|n| n == name))
+    }
+
+    pub fn is_recovery(&self) -> bool {
+        matches!(self.kind, NotificationKind::Recovered { .. })
+    }
+
+    /// The notifications merged in a digest, only available for a digest.
+    pub fn digested(&self) -> Option<&[Notification]> {
+        match &self.kind {
+            NotificationKind::Digest { notifications } => Some(notifications),
+            _ => None,
+        }
+    }
+
+    /// Since when the service is down, not available for the first failure notification.
+    pub fn down_since(&self) -> Option<&Zoned> {
+        match &self.kind {
+            NotificationKind::Down | NotificationKind::Digest { .. } => None,
+            NotificationKind::Escalated { down_since, .. }
+            | NotificationKind::Recovered { down_since } => Some(down_since),
+        }
+    }
+
+    /// The escalation tier reached, only available for an escalation.
+    pub fn escalation_tier(&self) -> Option<usize> {
+        match &self.kind {
+            NotificationKind::Escalated { tier, .. } => Some(*tier),
+            _ => None,
+        }
+    }
+
+    /// How long the service is down until the check of the notification.
+    /// It's truncated to seconds, as checks never run in a smaller interval.
+    fn down_for(&self) -> Option<SignedDuration> {
+        self.down_since().map(|down_since| {
+            SignedDuration::from_secs(self.result.checked_at.duration_since(down_since).as_secs())
+        })
+    }
+
+    /// How long the service was down, only available for a recovery.
+    pub fn outage(&self) -> Option<SignedDuration> {
+        self.down_for().filter(|_| self.is_recovery())
+    }
+
+    /// A short summary of the notification, used as title by the chat and push notifiers.
+    pub fn title(&self) -> String {
+        let service_name = &self.result.service_name;
+        match (&self.kind, self.down_for()) {
+            (NotificationKind::Digest { notifications }, _) => format!(
+                "{} notifications grouped in a digest: {}",
+                notifications.len(),
+                Notification::count_by_event(notifications)
+            ),
+            (NotificationKind::Recovered { .. }, Some(outage)) => {
+                format!("Service {service_name} is back up after {outage:#}")
+            }
+            (NotificationKind::Escalated { .. }, Some(down_for)) => format!(
+                "Service {service_name} is still down with status {} after {down_for:#}",
+                self.result.status
+            ),
+            _ => format!(
+                "Service {service_name} is down with status {}",
+                self.result.status
+            ),
+        }
+    }
+
+    /// The opening sentence of the long messages, like the email one.
+    pub fn intro(&self) -> String {
+        match (&self.kind, self.down_for()) {
+            (NotificationKind::Digest { .. }, _) => {
+                "Hello, several services changed state in a short time, here is a digest"
+                    .to_string()
+            }
+            (NotificationKind::Recovered { .. }, Some(outage)) => {
+                format!("Hello, a service is back up after an outage of {outage:#}")
+            }
+            (NotificationKind::Escalated { .. }, Some(down_for)) => {
+                format!("Hello, a service is still down after {down_for:#} and was escalated")
+            }
+            _ => {
+                "Hello, a service reported with an unexpected status through the last verification"
+                    .to_string()
+            }
+        }
+    }
+
+    /// How many notifications there are of each event, like "3 down, 1 recovered".
+    fn count_by_event(notifications: &[Notification]) -> String {
+        ["down", "escalated", "recovered"]
+            .into_iter()
+            .filter_map(|event| {
+                let count = notifications
+                    .iter()
+                    .filter(|notification| notification.kind.to_string() == event)
+                    .count();
+                (count > 0).then(|| format!("{count} {event}"))
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// The JSON representation of the notification, timestamps are in RFC 3339.
+    /// A digest holds the representation of each notification it merges.
+    pub fn payload(&self) -> Value {
+        let mut payload = json!({
+            "service": self.result.service_name,
+            "event": self.kind.to_string(),
+            "status": self.result.status.to_string(),
+            "message": self.result.message,
+            "checked_at": self.result.checked_at.timestamp().to_string(),
+            "down_since": self.down_since().map(|down_since| down_since.timestamp().to_string()),
+            "outage_seconds": self.outage().map(|outage| outage.as_secs()),
+            "escalation_tier": self.escalation_tier(),
+            "tags": self.tags,
+        });
+        if let Some(notifications) = self.digested() {
+            payload["notifications"] = notifications.iter().map(Notification::payload).collect();
+        }
+
+        payload
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(service_name: &str, status: CheckerStatus, message: &str) -> Notification {
+        let result = CheckerResult::new(service_name.to_string(), status, message.to_string());
+        Notification::new(result, NotificationKind::Down, vec![], None)
+    }
+
+    #[test]
+    fn digest_of_a_storm_fits_the_chat_limits() {
+        let message = "Server unavailable: Connection refused (os error 111) ".repeat(20);
+        let notifications: Vec<Notification> = (1..=40)
+            .map(|n| notification(&format!("service-{n}"), CheckerStatus::Error, &message))
+            .collect();
+        let digest = Notification::digest(notifications);
+
+        let lines: Vec<&str> = digest.result.message.lines().collect();
+        assert_eq!(lines.len(), DIGEST_MAX_ENTRIES + 1);
+        assert!(lines[0].starts_with("Service service-1 is down with status Error: Server"));
+        assert!(lines[..DIGEST_MAX_ENTRIES].iter().all(|line| {
+            line.ends_with("...") && line.chars().count() == DIGEST_MAX_LINE_LENGTH
+        }));
+        assert_eq!(lines[DIGEST_MAX_ENTRIES], "and 30 more");
+        assert!(digest.result.message.chars().count() <= 1024);
+        assert!(
+            digest
+                .result
+                .service_name
+                .starts_with("service-1, service-2, ")
+        );
+        assert!(
+            digest
+                .result
+                .service_name
+                .ends_with("service-10, and 30 more")
+        );
+        assert_eq!(digest.digested().map(<[Notification]>::len), Some(40));
+        assert_eq!(
+            digest.title(),
+            "40 notifications grouped in a digest: 40 down"
+        );
+    }
+
+    #[test]
+    fn digest_of_a_few_lists_all_of_them() {
+        let digest = Notification::digest(vec![
+            notification("web", CheckerStatus::Timeout, "Timed out"),
+            notification("db", CheckerStatus::Error, "Refused"),
+            notification("web", CheckerStatus::Success, "Ok"),
+        ]);
+        assert_eq!(digest.result.service_name, "web, db");
+        assert_eq!(
+            digest.result.message,
+            "Service web is down with status Timeout: Timed out\n\
+             Service db is down with status Error: Refused\n\
+             Service web is down with status Success: Ok"
+        );
+    }
+
+    #[test]
+    fn digest_has_the_worst_status() {
+        let cases = [
+            (vec![CheckerStatus::Success], CheckerStatus::Success),
+            (
+                vec![CheckerStatus::Success, CheckerStatus::Timeout],
+                CheckerStatus::Timeout,
+            ),
+            (
+                vec![
+                    CheckerStatus::Timeout,
+                    CheckerStatus::Error,
+                    CheckerStatus::Success,
+                ],
+                CheckerStatus::Error,
+            ),
+            (
+                vec![CheckerStatus::Error, CheckerStatus::Timeout],
+                CheckerStatus::Error,
+            ),
+        ];
+        for (statuses, expected) in cases {
+            let notifications = statuses
+                .iter()
+                .map(|status| notification("web", *status, ""))
+                .collect();
+            assert_eq!(
+                Notification::digest(notifications).result.status,
+                expected,
+                "status of a digest of {statuses:?}"
+            );
         }
     }
 }
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_PRIORITY: i64 = 4;
+
+/// Publishes the notification to a ntfy topic.
+#[derive(Debug, Clone)]
+pub struct NtfyNotifier {
+    client: Client,
+    server_url: Url,
+    topic: String,
+    priority: i64,
+    tags: Vec<String>,
+    access_token: Option<String>,
+}
+
+#[async_trait]
+impl Notifier for NtfyNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        // Recoveries use the default priority of ntfy, alerts the configured one.
+        let (priority, emoji) = if notification.is_recovery() {
+            (3, "white_check_mark")
+        } else {
+            (self.priority, "rotating_light")
+        };
+        let mut tags = vec![emoji.to_string()];
+        tags.extend(self.tags.iter().cloned());
+        tags.extend(notification.tags.iter().cloned());
+
+        let mut request = self.client.post(self.server_url.clone()).json(&json!({
+            "topic": self.topic,
+            "title": notification.title(),
+            "message": notification.result.message,
+            "priority": priority,
+            "tags": tags,
+        }));
+        if let Some(access_token) = &self.access_token {
+            request = request.bearer_auth(access_token);
+        }
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for NtfyNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        // ntfy receives JSON messages at the server root, the topic goes in the payload.
+        let mut server_url = http::url(data, ConfigKey::TopicUrl, NotificationType::Ntfy)?;
+        let topic = server_url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|topic| !topic.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                NotificationParseError::InvalidFormat(
+                    ConfigKey::TopicUrl,
+                    "the URL of a topic, like https://ntfy.sh/my-topic".to_string(),
+                )
+            })?;
+        if let Ok(mut segments) = server_url.path_segments_mut() {
+            segments.pop().push("");
+        }
+
+        let access_token = match &data[ConfigKey::TokenFile.as_ref()] {
+            Yaml::BadValue => None,
+            _ => Some(fields::secret_file(
+                data,
+                ConfigKey::TokenFile,
+                NotificationType::Ntfy,
+            )?),
+        };
+
+        Ok(NtfyNotifier {
+            client: http::client(data)?,
+            server_url,
+            topic,
+            priority: fields::optional_int(data, ConfigKey::Priority, 1..=5)?
+                .unwrap_or(DEFAULT_PRIORITY),
+            tags: fields::optional_list(data, ConfigKey::Tags)?,
+            access_token,
+        })
+    }
+}
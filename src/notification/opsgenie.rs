@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationKind, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_API_URL: &str = "https://api.opsgenie.com";
+const MAX_MESSAGE_LENGTH: usize = 130;
+
+/// Creates Opsgenie alerts aliased by the service name, and closes them on recovery.
+#[derive(Debug, Clone)]
+pub struct OpsgenieNotifier {
+    client: Client,
+    api_url: Url,
+    api_key: String,
+}
+
+impl OpsgenieNotifier {
+    fn priority(status: &CheckerStatus) -> &'static str {
+        match status {
+            CheckerStatus::Success => "P5",
+            CheckerStatus::Error => "P1",
+            CheckerStatus::Timeout => "P2",
+        }
+    }
+
+    /// Build an URL under the API URL, each segment is percent encoded.
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.api_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+}
+
+#[async_trait]
+impl Notifier for OpsgenieNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let exec_result = &notification.result;
+        let request = match notification.kind {
+            NotificationKind::Down | NotificationKind::Escalated { .. } => {
+                self.client.post(self.url(&["v2", "alerts"])).json(&json!({
+                    "message": notification
+                        .title()
+                        .chars()
+                        .take(MAX_MESSAGE_LENGTH)
+                        .collect::<String>(),
+                    "alias": exec_result.service_name,
+                    "description": exec_result.message,
+                    "priority": OpsgenieNotifier::priority(&exec_result.status),
+                    "tags": notification.tags,
+                    "source": "toktok",
+                    "details": {
+                        "status": exec_result.status.to_string(),
+                        "checked_at": exec_result.checked_at.timestamp().to_string(),
+                    },
+                }))
+            }
+            NotificationKind::Recovered { .. } => self
+                .client
+                .post(self.url(&["v2", "alerts", &exec_result.service_name, "close"]))
+                .query(&[("identifierType", "alias")])
+                .json(&json!({
+                    "source": "toktok",
+                    "note": notification.title(),
+                })),
+            // The alerts are identified by service, so each one keeps its own request
+            NotificationKind::Digest { ref notifications } => {
+                for notification in notifications {
+                    self.notify(notification).await?;
+                }
+                return Ok(());
+            }
+        };
+        let request = request.header("Authorization", format!("GenieKey {}", self.api_key));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for OpsgenieNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let api_key = fields::secret_file(data, ConfigKey::ApiKeyFile, NotificationType::Opsgenie)?;
+        let api_url = fields::optional_str(data, ConfigKey::ApiUrl)?
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let api_url = Url::parse(&api_url).map_err(|e| {
+            NotificationParseError::InternalParse(format!(
+                "The opsgenie '{}' is not a valid URL: {e}",
+                ConfigKey::ApiUrl
+            ))
+        })?;
+
+        Ok(OpsgenieNotifier {
+            client: http::client(data)?,
+            api_url,
+            api_key,
+        })
+    }
+}
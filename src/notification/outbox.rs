@@ -0,0 +1,291 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::Duration,
+};
+
+use jiff::{SignedDuration, Timestamp};
+use serde::{Deserialize, Serialize};
+use tracing::{Level, event};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{Notification, error::NotificationParseError, fields},
+    parser::ConfigKey,
+};
+
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30 * 60);
+const DEFAULT_MAX_AGE: SignedDuration = SignedDuration::from_hours(24);
+
+/// How the failed deliveries are retried, defined by the optional `outbox` section.
+/// The wait before a new attempt starts at `backoff` and doubles up to `max_backoff`,
+/// a notification still not delivered after `max_age` is dropped.
+/// The default `path` is in the temp directory, often a tmpfs cleared on reboot,
+/// so the pending notifications only survive a restart of the program.
+#[derive(Debug, Clone)]
+pub struct OutboxPolicy {
+    pub path: PathBuf,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_age: SignedDuration,
+}
+
+impl OutboxPolicy {
+    /// The wait before the next attempt of a notification which already failed `attempts` times.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for OutboxPolicy {
+    fn default() -> Self {
+        Self {
+            path: std::env::temp_dir().join("toktok").join("outbox.json"),
+            backoff: DEFAULT_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+}
+
+impl TryFrom<&Yaml> for OutboxPolicy {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let mut policy = OutboxPolicy::default();
+        if let Some(path) = fields::optional_str(data, ConfigKey::Path)? {
+            policy.path = PathBuf::from(path);
+        }
+        if let Some(backoff) = fields::optional_positive(data, ConfigKey::Backoff)? {
+            policy.backoff = Duration::from_secs(backoff);
+        }
+        if let Some(max_backoff) = fields::optional_positive(data, ConfigKey::MaxBackoff)? {
+            policy.max_backoff = Duration::from_secs(max_backoff);
+        }
+        if let Some(max_age) = fields::optional_positive(data, ConfigKey::MaxAge)? {
+            policy.max_age = SignedDuration::from_mins(max_age as i64);
+        }
+
+        if policy.max_backoff < policy.backoff {
+            return Err(NotificationParseError::InternalParse(format!(
+                "Key '{}' cannot be lower than '{}'",
+                ConfigKey::MaxBackoff,
+                ConfigKey::Backoff
+            )));
+        }
+
+        Ok(policy)
+    }
+}
+
+/// A notification waiting to be delivered by a notifier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub notification: Notification,
+    pub queued_at: Timestamp,
+    pub attempts: u32,
+    pub next_attempt_at: Timestamp,
+}
+
+impl OutboxEntry {
+    pub fn new(notification: Notification) -> Self {
+        let now = Timestamp::now();
+        Self {
+            notification,
+            queued_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, max_age: SignedDuration) -> bool {
+        self.queued_at.duration_until(Timestamp::now()) > max_age
+    }
+}
+
+/// The pending notifications of every notifier, persisted in a JSON file,
+/// so the notifications which failed are not lost when the program restarts.
+#[derive(Debug)]
+pub struct Outbox {
+    path: PathBuf,
+    pending: Mutex<BTreeMap<String, VecDeque<OutboxEntry>>>,
+}
+
+impl Outbox {
+    /// Load the notifications left pending by a previous execution.
+    /// An unreadable file is reported and ignored, as it must not prevent the monitoring.
+    pub fn load(path: PathBuf) -> Self {
+        let pending = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+                event!(
+                    Level::ERROR,
+                    path = %path.display(),
+                    error = %err,
+                    "Invalid outbox file, the pending notifications were discarded"
+                );
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self {
+            path,
+            pending: Mutex::new(pending),
+        }
+    }
+
+    /// Take the notifications left pending for the notifier named `name`.
+    pub fn take(&self, name: &str) -> VecDeque<OutboxEntry> {
+        self.pending
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Keep only the pending notifications of the notifiers still configured.
+    pub fn retain(&self, names: &[String]) {
+        let mut pending = self.pending.lock().unwrap();
+        for (name, entries) in pending.iter().filter(|(name, _)| !names.contains(name)) {
+            event!(
+                Level::WARN,
+                notifier = name,
+                dropped = entries.len(),
+                "The notifier is not configured anymore, its pending notifications were dropped"
+            );
+        }
+        let len = pending.len();
+        pending.retain(|name, _| names.contains(name));
+        if pending.len() != len {
+            self.write(&pending);
+        }
+    }
+
+    /// Replace the pending notifications of the notifier named `name` and persist all of them.
+    pub fn save(&self, name: &str, entries: &VecDeque<OutboxEntry>) {
+        let mut pending = self.pending.lock().unwrap();
+        if entries.is_empty() {
+            pending.remove(name);
+        } else {
+            pending.insert(name.to_string(), entries.clone());
+        }
+        self.write(&pending);
+    }
+
+    /// Write the file through a temporary one, so a crash never leaves it half written.
+    fn write(&self, pending: &BTreeMap<String, VecDeque<OutboxEntry>>) {
+        let tmp_path = self.path.with_extension("tmp");
+        let result = serde_json::to_vec(pending)
+            .map_err(std::io::Error::from)
+            .and_then(|content| {
+                if let Some(dir) = self.path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&tmp_path, content)?;
+                fs::rename(&tmp_path, &self.path)
+            });
+
+        if let Err(err) = result {
+            event!(
+                Level::ERROR,
+                path = %self.path.display(),
+                error = %err,
+                "Unable to persist the pending notifications"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::{notification, section};
+
+    #[test]
+    fn backoff_doubles_up_to_the_max() {
+        let policy = OutboxPolicy {
+            backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(300),
+            ..OutboxPolicy::default()
+        };
+        let cases = [
+            (0, 30),
+            (1, 30),
+            (2, 60),
+            (3, 120),
+            (4, 240),
+            (5, 300),
+            (u32::MAX, 300),
+        ];
+        for (attempts, backoff) in cases {
+            assert_eq!(
+                policy.backoff(attempts),
+                Duration::from_secs(backoff),
+                "backoff after {attempts} attempts"
+            );
+        }
+    }
+
+    #[test]
+    fn policy_from_yaml() {
+        let policy = OutboxPolicy::try_from(&section(
+            "path: /var/lib/toktok/outbox.json\nbackoff: 10\nmax_backoff: 60\nmax_age: 30",
+        ))
+        .unwrap();
+        assert_eq!(policy.path, PathBuf::from("/var/lib/toktok/outbox.json"));
+        assert_eq!(policy.backoff, Duration::from_secs(10));
+        assert_eq!(policy.max_backoff, Duration::from_secs(60));
+        assert_eq!(policy.max_age, SignedDuration::from_mins(30));
+
+        let default = OutboxPolicy::try_from(&section("{}")).unwrap();
+        assert_eq!(default.backoff, DEFAULT_BACKOFF);
+        assert_eq!(default.max_backoff, DEFAULT_MAX_BACKOFF);
+        assert_eq!(default.max_age, DEFAULT_MAX_AGE);
+
+        for invalid in ["backoff: 60\nmax_backoff: 30", "backoff: 0", "max_age: -1"] {
+            assert!(
+                OutboxPolicy::try_from(&section(invalid)).is_err(),
+                "{invalid} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn entry_expires_after_the_max_age() {
+        let mut entry = OutboxEntry::new(notification("web", "Refused"));
+        assert!(!entry.is_expired(SignedDuration::from_mins(1)));
+        entry.queued_at -= SignedDuration::from_mins(2);
+        assert!(entry.is_expired(SignedDuration::from_mins(1)));
+    }
+
+    #[test]
+    fn pending_notifications_survive_a_restart() {
+        let path = std::env::temp_dir()
+            .join("toktok-tests")
+            .join(format!("outbox-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let outbox = Outbox::load(path.clone());
+        let entries = VecDeque::from([OutboxEntry::new(notification("web", "Refused"))]);
+        outbox.save("hook", &entries);
+        outbox.save("mailer", &entries);
+
+        let outbox = Outbox::load(path.clone());
+        assert_eq!(outbox.take("hook").len(), 1);
+        outbox.retain(&["mailer".to_string()]);
+        outbox.save("mailer", &VecDeque::new());
+
+        let outbox = Outbox::load(path.clone());
+        assert!(outbox.take("hook").is_empty());
+        assert!(outbox.take("mailer").is_empty());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(Outbox::load(path.clone()).take("hook").is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationKind, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_API_URL: &str = "https://events.pagerduty.com";
+
+/// Opens and resolves PagerDuty incidents through the Events API v2.
+/// Each service has a stable `dedup_key`,
+/// so its recovery resolves the incident opened by its failure.
+#[derive(Debug, Clone)]
+pub struct PagerDutyNotifier {
+    client: Client,
+    enqueue_url: Url,
+    routing_key: Option<String>,
+    service_routing_keys: HashMap<String, String>,
+}
+
+impl PagerDutyNotifier {
+    fn dedup_key(service_name: &str) -> String {
+        format!("toktok/{service_name}")
+    }
+
+    fn severity(status: &CheckerStatus) -> &'static str {
+        match status {
+            CheckerStatus::Success => "info",
+            CheckerStatus::Error => "critical",
+            CheckerStatus::Timeout => "error",
+        }
+    }
+
+    fn routing_key(&self, service_name: &str) -> Option<&String> {
+        self.service_routing_keys
+            .get(service_name)
+            .or(self.routing_key.as_ref())
+    }
+}
+
+#[async_trait]
+impl Notifier for PagerDutyNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let exec_result = &notification.result;
+        let service_name = &exec_result.service_name;
+        let routing_key = || {
+            self.routing_key(service_name).ok_or_else(|| {
+                NotificationError::Misconfigured(format!(
+                    "No PagerDuty routing key for the service '{service_name}'"
+                ))
+            })
+        };
+        let dedup_key = PagerDutyNotifier::dedup_key(service_name);
+        let payload = match notification.kind {
+            NotificationKind::Down | NotificationKind::Escalated { .. } => json!({
+                "routing_key": routing_key()?,
+                "event_action": "trigger",
+                "dedup_key": dedup_key,
+                "payload": {
+                    "summary": notification.title(),
+                    "source": service_name,
+                    "severity": PagerDutyNotifier::severity(&exec_result.status),
+                    "timestamp": exec_result.checked_at.timestamp().to_string(),
+                    "component": service_name,
+                    "custom_details": {
+                        "status": exec_result.status.to_string(),
+                        "message": exec_result.message,
+                    },
+                },
+            }),
+            NotificationKind::Recovered { .. } => json!({
+                "routing_key": routing_key()?,
+                "event_action": "resolve",
+                "dedup_key": dedup_key,
+            }),
+            // The incidents are deduplicated by service, so each one keeps its own event
+            NotificationKind::Digest { ref notifications } => {
+                for notification in notifications {
+                    self.notify(notification).await?;
+                }
+                return Ok(());
+            }
+        };
+
+        let request = self.client.post(self.enqueue_url.clone()).json(&payload);
+
+        http::send(request).await
+    }
+
+    fn check_services(&self, routed: &[String], services: &[String]) -> Result<(), String> {
+        if let Some(unknown) = self
+            .service_routing_keys
+            .keys()
+            .find(|service| !services.contains(service))
+        {
+            return Err(format!(
+                "The '{}' has a routing key for the service '{unknown}', which is not defined",
+                ConfigKey::ServiceRoutingKeyFiles
+            ));
+        }
+        if let Some(service) = routed
+            .iter()
+            .find(|service| self.routing_key(service).is_none())
+        {
+            return Err(format!("No routing key for the service '{service}'"));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&Yaml> for PagerDutyNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let routing_key = fields::optional_str(data, ConfigKey::RoutingKeyFile)?
+            .map(|path| fields::read_secret(&path, ConfigKey::RoutingKeyFile))
+            .transpose()?;
+
+        let invalid_format = || {
+            NotificationParseError::InvalidFormat(
+                ConfigKey::ServiceRoutingKeyFiles,
+                "a map of service names to routing key files".to_string(),
+            )
+        };
+        let service_routing_keys = match &data[ConfigKey::ServiceRoutingKeyFiles.as_ref()] {
+            Yaml::Hash(files) => files
+                .iter()
+                .map(|(service, path)| match (service, path) {
+                    (Yaml::String(service), Yaml::String(path)) => {
+                        let key = fields::read_secret(path, ConfigKey::ServiceRoutingKeyFiles)?;
+                        Ok((service.clone(), key))
+                    }
+                    _ => Err(invalid_format()),
+                })
+                .collect::<Result<HashMap<String, String>, NotificationParseError>>()?,
+            Yaml::BadValue => HashMap::new(),
+            _ => return Err(invalid_format()),
+        };
+
+        if routing_key.is_none() && service_routing_keys.is_empty() {
+            return Err(NotificationParseError::KeyNotFoundAt(
+                ConfigKey::RoutingKeyFile,
+                NotificationType::PagerDuty,
+            ));
+        }
+
+        let api_url = fields::optional_str(data, ConfigKey::ApiUrl)?
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let enqueue_url = Url::parse(&format!("{}/v2/enqueue", api_url.trim_end_matches('/')))
+            .map_err(|e| {
+                NotificationParseError::InternalParse(format!(
+                    "The pagerduty '{}' is not a valid URL: {e}",
+                    ConfigKey::ApiUrl
+                ))
+            })?;
+
+        Ok(PagerDutyNotifier {
+            client: http::client(data)?,
+            enqueue_url,
+            routing_key,
+            service_routing_keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::{Listener, notification, secret_file, section};
+
+    #[tokio::test]
+    async fn reads_the_routing_keys_from_files() {
+        let mut listener = Listener::start(202).await;
+        let key = secret_file("pagerduty.key", "global-key\n");
+        let db_key = secret_file("pagerduty-db.key", " db-key \nignored\n");
+        let notifier = PagerDutyNotifier::try_from(&section(&format!(
+            "routing_key_file: {key}\nservice_routing_key_files:\n  db.main: {db_key}\n\
+             api_url: {}/",
+            listener.url
+        )))
+        .unwrap();
+        for service in ["db.main", "api"] {
+            notifier
+                .notify(&notification(service, "Connection refused"))
+                .await
+                .unwrap();
+        }
+
+        let requests = listener.requests();
+        let routing_keys: Vec<_> = requests
+            .iter()
+            .map(|request| request.json()["routing_key"].clone())
+            .collect();
+        assert_eq!(routing_keys, [json!("db-key"), json!("global-key")]);
+        for request in &requests {
+            assert_eq!(request.path, "/v2/enqueue");
+            assert_eq!(request.json()["event_action"], "trigger");
+        }
+    }
+
+    #[test]
+    fn rejects_a_missing_or_empty_key_file() {
+        let empty = secret_file("pagerduty-empty.key", "\n");
+        let cases = [
+            "api_url: http://localhost".to_string(),
+            format!("routing_key_file: {empty}"),
+            "routing_key_file: /nonexistent/pagerduty.key".to_string(),
+            format!("service_routing_key_files:\n  db.main: {empty}"),
+            "service_routing_key_files: [db.main]".to_string(),
+        ];
+        for yaml in cases {
+            assert!(
+                PagerDutyNotifier::try_from(&section(&yaml)).is_err(),
+                "accepted {yaml}"
+            );
+        }
+    }
+
+    #[test]
+    fn check_services_needs_a_key_for_every_routed_service() {
+        let db_key = secret_file("pagerduty-check.key", "db-key");
+        let notifier = PagerDutyNotifier::try_from(&section(&format!(
+            "service_routing_key_files:\n  db.main: {db_key}"
+        )))
+        .unwrap();
+        let services = ["db.main".to_string(), "api".to_string()];
+
+        assert!(notifier.check_services(&services[..1], &services).is_ok());
+        assert!(notifier.check_services(&services, &services).is_err());
+        assert!(notifier.check_services(&[], &services[1..]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_API_URL: &str = "https://api.pushover.net";
+const DEFAULT_PRIORITY: i64 = 1;
+
+/// Pushes the notification to a Pushover user or group.
+#[derive(Debug, Clone)]
+pub struct PushoverNotifier {
+    client: Client,
+    messages_url: Url,
+    app_token: String,
+    user_key: String,
+    priority: i64,
+}
+
+#[async_trait]
+impl Notifier for PushoverNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        // Recoveries are sent with the normal priority, alerts with the configured one.
+        let priority = if notification.is_recovery() {
+            0
+        } else {
+            self.priority
+        };
+        let request = self.client.post(self.messages_url.clone()).json(&json!({
+            "token": self.app_token,
+            "user": self.user_key,
+            "title": notification.title(),
+            "message": notification.result.message,
+            "priority": priority,
+            "timestamp": notification.result.checked_at.timestamp().as_second(),
+        }));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for PushoverNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let api_url = fields::optional_str(data, ConfigKey::ApiUrl)?
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let messages_url = Url::parse(&format!(
+            "{}/1/messages.json",
+            api_url.trim_end_matches('/')
+        ))
+        .map_err(|e| {
+            NotificationParseError::InternalParse(format!(
+                "The pushover '{}' is not a valid URL: {e}",
+                ConfigKey::ApiUrl
+            ))
+        })?;
+
+        Ok(PushoverNotifier {
+            client: http::client(data)?,
+            messages_url,
+            app_token: fields::secret_file(
+                data,
+                ConfigKey::AppTokenFile,
+                NotificationType::Pushover,
+            )?,
+            user_key: fields::secret_file(
+                data,
+                ConfigKey::UserKeyFile,
+                NotificationType::Pushover,
+            )?,
+            // The emergency priority 2 requires extra parameters, so it's not accepted.
+            priority: fields::optional_int(data, ConfigKey::Priority, -2..=1)?
+                .unwrap_or(DEFAULT_PRIORITY),
+        })
+    }
+}
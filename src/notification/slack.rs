@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::{Value, json};
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+/// Sends the notification to a Slack incoming webhook as a Block Kit message.
+#[derive(Debug, Clone)]
+pub struct SlackNotifier {
+    client: Client,
+    webhook_url: Url,
+    channel: Option<String>,
+    username: Option<String>,
+    icon: Option<String>,
+}
+
+impl SlackNotifier {
+    fn color(status: &CheckerStatus) -> &'static str {
+        match status {
+            CheckerStatus::Success => "#2eb67d",
+            CheckerStatus::Error => "#e01e5a",
+            CheckerStatus::Timeout => "#ecb22e",
+        }
+    }
+
+    /// Escape the characters Slack reserves for the links and mentions of a mrkdwn text.
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    fn field(name: &str, value: &dyn Display) -> Value {
+        let value = SlackNotifier::escape(&value.to_string());
+        json!({ "type": "mrkdwn", "text": format!("*{name}:*\n{value}") })
+    }
+
+    fn payload(&self, notification: &Notification) -> Value {
+        let exec_result = &notification.result;
+        let checked_at = exec_result.checked_at.strftime("%Y-%m-%d %H:%M:%S %Z");
+        let mut fields = vec![
+            SlackNotifier::field("Service", &exec_result.service_name),
+            SlackNotifier::field("Status", &exec_result.status),
+            SlackNotifier::field("Checked at", &checked_at),
+        ];
+        if let Some(outage) = notification.outage() {
+            fields.push(SlackNotifier::field("Outage", &format!("{outage:#}")));
+        }
+
+        let mut payload = json!({
+            "text": SlackNotifier::escape(&notification.title()),
+            "attachments": [{
+                "color": SlackNotifier::color(&exec_result.status),
+                "blocks": [
+                    {
+                        "type": "header",
+                        "text": { "type": "plain_text", "text": notification.title() },
+                    },
+                    { "type": "section", "fields": fields },
+                    {
+                        "type": "section",
+                        "text": SlackNotifier::field("Message", &exec_result.message),
+                    },
+                ],
+            }],
+        });
+
+        if let Some(channel) = &self.channel {
+            payload["channel"] = json!(channel);
+        }
+        if let Some(username) = &self.username {
+            payload["username"] = json!(username);
+        }
+        match &self.icon {
+            Some(icon) if icon.starts_with(':') => payload["icon_emoji"] = json!(icon),
+            Some(icon) => payload["icon_url"] = json!(icon),
+            None => {}
+        }
+
+        payload
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let request = self
+            .client
+            .post(self.webhook_url.clone())
+            .json(&self.payload(notification));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for SlackNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        Ok(SlackNotifier {
+            client: http::client(data)?,
+            webhook_url: http::url(data, ConfigKey::WebhookUrl, NotificationType::Slack)?,
+            channel: fields::optional_str(data, ConfigKey::Channel)?,
+            username: fields::optional_str(data, ConfigKey::Username)?,
+            icon: fields::optional_str(data, ConfigKey::Icon)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::{notification, section};
+
+    #[test]
+    fn mrkdwn_values_are_escaped() {
+        let notifier =
+            SlackNotifier::try_from(&section("webhook_url: https://hooks.slack.test/T0/B0/x"))
+                .unwrap();
+        let payload = notifier.payload(&notification(
+            "a&b",
+            "The body doesn't contain <title>Home</title>",
+        ));
+        let blocks = &payload["attachments"][0]["blocks"];
+        assert_eq!(payload["text"], "Service a&amp;b is down with status Error");
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "Service a&b is down with status Error"
+        );
+        assert_eq!(blocks[1]["fields"][0]["text"], "*Service:*\na&amp;b");
+        assert_eq!(
+            blocks[2]["text"]["text"],
+            "*Message:*\nThe body doesn't contain &lt;title&gt;Home&lt;/title&gt;"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use tracing::{Level, event};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_API_URL: &str = "https://api.twilio.com";
+/// The length of a single SMS segment with the GSM charset.
+const MAX_SMS_LENGTH: usize = 160;
+
+/// Sends the notification as a SMS through a Twilio compatible REST API.
+#[derive(Debug, Clone)]
+pub struct SmsNotifier {
+    client: Client,
+    messages_url: Url,
+    account_sid: String,
+    auth_token: String,
+    from: String,
+    to: Vec<String>,
+}
+
+impl SmsNotifier {
+    /// A single segment text, the message is truncated to fit after the title.
+    fn text(notification: &Notification) -> String {
+        let text = format!("{}: {}", notification.title(), notification.result.message);
+        if text.chars().count() <= MAX_SMS_LENGTH {
+            return text;
+        }
+
+        let mut truncated: String = text.chars().take(MAX_SMS_LENGTH - 3).collect();
+        truncated.push_str("...");
+        truncated
+    }
+}
+
+#[async_trait]
+impl Notifier for SmsNotifier {
+    /// The message is sent to every number, even when some of them fail.
+    /// A failure of any of them fails the notification, preferring an error which
+    /// a new attempt may solve, as a missed alert is worse than a repeated one.
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let text = SmsNotifier::text(notification);
+        let mut error: Option<NotificationError> = None;
+        for to in &self.to {
+            let request = self
+                .client
+                .post(self.messages_url.clone())
+                .basic_auth(&self.account_sid, Some(&self.auth_token))
+                .form(&[("To", to), ("From", &self.from), ("Body", &text)]);
+
+            if let Err(err) = http::send(request).await {
+                event!(
+                    Level::WARN,
+                    to,
+                    error = %err,
+                    "Error sending the SMS notification to a number"
+                );
+                if error.as_ref().is_none_or(|error| !error.is_retryable()) {
+                    error = Some(err);
+                }
+            }
+        }
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl TryFrom<&Yaml> for SmsNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let account_sid = fields::required_str(data, ConfigKey::AccountSid, NotificationType::Sms)?;
+        let api_url = fields::optional_str(data, ConfigKey::ApiUrl)?
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let mut messages_url = Url::parse(&api_url).map_err(|e| {
+            NotificationParseError::InternalParse(format!(
+                "The sms '{}' is not a valid URL: {e}",
+                ConfigKey::ApiUrl
+            ))
+        })?;
+        if let Ok(mut segments) = messages_url.path_segments_mut() {
+            segments.pop_if_empty().extend([
+                "2010-04-01",
+                "Accounts",
+                &account_sid,
+                "Messages.json",
+            ]);
+        }
+
+        Ok(SmsNotifier {
+            client: http::client(data)?,
+            messages_url,
+            auth_token: fields::secret_file(data, ConfigKey::AuthTokenFile, NotificationType::Sms)?,
+            account_sid,
+            from: fields::required_str(data, ConfigKey::SmsFrom, NotificationType::Sms)?,
+            to: fields::required_list(data, ConfigKey::SmsTo, NotificationType::Sms)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::{Listener, notification, secret_file, section};
+
+    #[test]
+    fn text_fits_a_single_segment() {
+        let title = "Service web is down with status Error: ";
+        let cases = [
+            ("Refused".to_string(), format!("{title}Refused")),
+            ("a".repeat(121), format!("{title}{}", "a".repeat(121))),
+            ("a".repeat(122), format!("{title}{}...", "a".repeat(118))),
+            ("é".repeat(200), format!("{title}{}...", "é".repeat(118))),
+        ];
+        for (message, text) in cases {
+            let sms = SmsNotifier::text(&notification("web", &message));
+            assert_eq!(sms, text);
+            assert!(sms.chars().count() <= MAX_SMS_LENGTH);
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_the_message_to_every_number() {
+        let mut listener = Listener::start(201).await;
+        let token = secret_file("sms.token", "secret");
+        let notifier = SmsNotifier::try_from(&section(&format!(
+            "account_sid: AC123\nauth_token_file: {token}\nfrom: '+15550001'\n\
+             to: ['+15550002', '+15550003']\napi_url: {}",
+            listener.url
+        )))
+        .unwrap();
+        notifier
+            .notify(&notification("web", "Refused"))
+            .await
+            .unwrap();
+
+        let requests = listener.requests();
+        let to: Vec<Option<String>> = requests.iter().map(|request| request.form("To")).collect();
+        assert_eq!(
+            to,
+            [Some("+15550002".to_string()), Some("+15550003".to_string())]
+        );
+        for request in &requests {
+            assert_eq!(request.method, "POST");
+            assert_eq!(request.path, "/2010-04-01/Accounts/AC123/Messages.json");
+            assert_eq!(
+                request.header("authorization"),
+                Some("Basic QUMxMjM6c2VjcmV0")
+            );
+            assert_eq!(request.form("From").as_deref(), Some("+15550001"));
+            assert_eq!(
+                request.form("Body").as_deref(),
+                Some("Service web is down with status Error: Refused")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn failure_of_a_number_is_retried() {
+        let listener = Listener::start(503).await;
+        let token = secret_file("sms-retried.token", "secret");
+        let notifier = SmsNotifier::try_from(&section(&format!(
+            "account_sid: AC123\nauth_token_file: {token}\nfrom: '+15550001'\n\
+             to: ['+15550002']\napi_url: {}",
+            listener.url
+        )))
+        .unwrap();
+        let err = notifier
+            .notify(&notification("web", "Refused"))
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+    }
+}
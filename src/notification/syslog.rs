@@ -0,0 +1,344 @@
+use std::{fmt::Display, io, net::SocketAddr, str::FromStr, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UdpSocket, lookup_host},
+    time,
+};
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_UNIX_SOCKET: &str = "/dev/log";
+const DEFAULT_APP_NAME: &str = "toktok";
+/// The private enterprise number reserved for documentation, used by the structured data id.
+const SD_ID: &str = "toktok@32473";
+const TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+enum SyslogTransport {
+    Unix(String),
+    Udp(String),
+    Tcp(String),
+}
+
+/// The syslog severities, from RFC 5424.
+#[derive(Debug, Clone, Copy)]
+enum Severity {
+    Emerg,
+    Alert,
+    Crit,
+    Err,
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+impl FromStr for Severity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "emerg" => Ok(Severity::Emerg),
+            "alert" => Ok(Severity::Alert),
+            "crit" => Ok(Severity::Crit),
+            "err" => Ok(Severity::Err),
+            "warning" => Ok(Severity::Warning),
+            "notice" => Ok(Severity::Notice),
+            "info" => Ok(Severity::Info),
+            "debug" => Ok(Severity::Debug),
+            _ => Err(format!("Is not a valid severity: {s}")),
+        }
+    }
+}
+
+/// The severity used for each `CheckerStatus`.
+#[derive(Debug, Clone)]
+struct SeverityMapping {
+    success: Severity,
+    error: Severity,
+    timeout: Severity,
+}
+impl SeverityMapping {
+    fn get(&self, status: &CheckerStatus) -> Severity {
+        match status {
+            CheckerStatus::Success => self.success,
+            CheckerStatus::Error => self.error,
+            CheckerStatus::Timeout => self.timeout,
+        }
+    }
+}
+impl Default for SeverityMapping {
+    fn default() -> Self {
+        Self {
+            success: Severity::Notice,
+            error: Severity::Err,
+            timeout: Severity::Warning,
+        }
+    }
+}
+
+/// Sends the notification to a syslog server in the RFC 5424 format.
+#[derive(Debug, Clone)]
+pub struct SyslogNotifier {
+    transport: SyslogTransport,
+    facility: u8,
+    severities: SeverityMapping,
+    hostname: String,
+    app_name: String,
+}
+
+impl SyslogNotifier {
+    fn facility(name: &str) -> Option<u8> {
+        let facility = match name {
+            "kern" => 0,
+            "user" => 1,
+            "mail" => 2,
+            "daemon" => 3,
+            "auth" => 4,
+            "syslog" => 5,
+            "lpr" => 6,
+            "news" => 7,
+            "uucp" => 8,
+            "cron" => 9,
+            "authpriv" => 10,
+            "ftp" => 11,
+            local if local.starts_with("local") => match local[5..].parse::<u8>() {
+                Ok(n) if n <= 7 => 16 + n,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(facility)
+    }
+
+    /// Escape the characters reserved inside a structured data param value.
+    fn escape_param(value: &dyn Display) -> String {
+        value
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace(']', "\\]")
+    }
+
+    fn message(&self, notification: &Notification) -> String {
+        let exec_result = &notification.result;
+        let priority = self.facility * 8 + self.severities.get(&exec_result.status) as u8;
+        let structured_data = format!(
+            "[{SD_ID} service=\"{}\" event=\"{}\" status=\"{}\"]",
+            SyslogNotifier::escape_param(&exec_result.service_name),
+            notification.kind,
+            exec_result.status
+        );
+        // RFC 5424 allows at most 6 digits for the fraction of second
+        format!(
+            "<{priority}>1 {:.6} {} {} {} {} {structured_data} {}: {}",
+            exec_result.checked_at.timestamp(),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            notification.kind.to_string().to_uppercase(),
+            notification.title(),
+            exec_result.message
+        )
+    }
+
+    async fn resolve(address: &str) -> io::Result<SocketAddr> {
+        lookup_host(address).await?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "No IP resolution for the address")
+        })
+    }
+
+    async fn send(&self, message: &str) -> io::Result<()> {
+        match &self.transport {
+            #[cfg(unix)]
+            SyslogTransport::Unix(path) => {
+                let socket = tokio::net::UnixDatagram::unbound()?;
+                socket.send_to(message.as_bytes(), path).await?;
+            }
+            #[cfg(not(unix))]
+            SyslogTransport::Unix(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Unix sockets are not supported in this platform",
+                ));
+            }
+            SyslogTransport::Udp(address) => {
+                let socket_addr = SyslogNotifier::resolve(address).await?;
+                let local_addr = if socket_addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                let socket = UdpSocket::bind(local_addr).await?;
+                socket.send_to(message.as_bytes(), socket_addr).await?;
+            }
+            SyslogTransport::Tcp(address) => {
+                let socket_addr = SyslogNotifier::resolve(address).await?;
+                // Octet counting framing, from RFC 6587.
+                let frame = format!("{} {message}", message.len());
+                time::timeout(TCP_TIMEOUT, async {
+                    let mut stream = TcpStream::connect(socket_addr).await?;
+                    stream.write_all(frame.as_bytes()).await
+                })
+                .await
+                .map_err(|_| {
+                    io::Error::new(io::ErrorKind::TimedOut, "Timed out sending the message")
+                })??;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for SyslogNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        Ok(self.send(&self.message(notification)).await?)
+    }
+}
+
+impl TryFrom<&Yaml> for SyslogNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let address = fields::optional_str(data, ConfigKey::Address)?;
+        let transport = match fields::optional_str(data, ConfigKey::Transport)?.as_deref() {
+            None | Some("unix") => {
+                SyslogTransport::Unix(address.unwrap_or_else(|| DEFAULT_UNIX_SOCKET.to_string()))
+            }
+            Some(transport @ ("udp" | "tcp")) => {
+                let address = match address {
+                    Some(address) if address.contains(':') => address,
+                    Some(_) => {
+                        return Err(NotificationParseError::InvalidFormat(
+                            ConfigKey::Address,
+                            "a value with pattern HOST:PORT".to_string(),
+                        ));
+                    }
+                    None => {
+                        return Err(NotificationParseError::KeyNotFoundAt(
+                            ConfigKey::Address,
+                            NotificationType::Syslog,
+                        ));
+                    }
+                };
+                if transport == "udp" {
+                    SyslogTransport::Udp(address)
+                } else {
+                    SyslogTransport::Tcp(address)
+                }
+            }
+            Some(_) => {
+                return Err(NotificationParseError::InvalidFormat(
+                    ConfigKey::Transport,
+                    "one of unix, udp or tcp".to_string(),
+                ));
+            }
+        };
+
+        let facility = match fields::optional_str(data, ConfigKey::Facility)? {
+            Some(facility) => SyslogNotifier::facility(&facility).ok_or_else(|| {
+                NotificationParseError::InvalidFormat(
+                    ConfigKey::Facility,
+                    "a facility name like daemon, user or local0 to local7".to_string(),
+                )
+            })?,
+            None => SyslogNotifier::facility("daemon").unwrap(),
+        };
+
+        let mut severities = SeverityMapping::default();
+        match &data[ConfigKey::Severity.as_ref()] {
+            Yaml::Hash(mapping) => {
+                for (status, severity) in mapping.iter() {
+                    let severity = severity
+                        .as_str()
+                        .and_then(|severity| Severity::from_str(severity).ok());
+                    match (status.as_str(), severity) {
+                        (Some("success"), Some(severity)) => severities.success = severity,
+                        (Some("error"), Some(severity)) => severities.error = severity,
+                        (Some("timeout"), Some(severity)) => severities.timeout = severity,
+                        _ => {
+                            return Err(NotificationParseError::InvalidFormat(
+                                ConfigKey::Severity,
+                                "a map of success, error or timeout to a severity like err"
+                                    .to_string(),
+                            ));
+                        }
+                    }
+                }
+            }
+            Yaml::BadValue => {}
+            _ => {
+                return Err(NotificationParseError::InvalidFormat(
+                    ConfigKey::Severity,
+                    "a map of success, error or timeout to a severity like err".to_string(),
+                ));
+            }
+        }
+
+        // The hostname is read from the kernel when not informed, as the std has no API for it.
+        let hostname = match fields::optional_str(data, ConfigKey::Hostname)? {
+            Some(hostname) => hostname,
+            None => std::fs::read_to_string("/proc/sys/kernel/hostname")
+                .map(|hostname| hostname.trim().to_string())
+                .ok()
+                .filter(|hostname| !hostname.is_empty())
+                .unwrap_or_else(|| "-".to_string()),
+        };
+
+        Ok(SyslogNotifier {
+            transport,
+            facility,
+            severities,
+            hostname,
+            app_name: fields::optional_str(data, ConfigKey::AppName)?
+                .unwrap_or_else(|| DEFAULT_APP_NAME.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Zoned;
+
+    use super::*;
+    use crate::notification::testing::{notification, section};
+
+    #[test]
+    fn message_has_a_microsecond_timestamp() {
+        let notifier = SyslogNotifier::try_from(&section(
+            "transport: udp\naddress: 127.0.0.1:514\nfacility: local0\nhostname: web01",
+        ))
+        .unwrap();
+        let cases = [
+            (
+                "2026-01-02T03:04:05.123456789Z",
+                "2026-01-02T03:04:05.123456Z",
+            ),
+            ("2026-01-02T03:04:05.1Z", "2026-01-02T03:04:05.100000Z"),
+            ("2026-01-02T03:04:05Z", "2026-01-02T03:04:05.000000Z"),
+        ];
+        for (checked_at, timestamp) in cases {
+            let mut notification = notification("db \"main\"", "Refused");
+            notification.result.checked_at = format!("{checked_at}[UTC]").parse::<Zoned>().unwrap();
+            assert_eq!(
+                notifier.message(&notification),
+                format!(
+                    "<131>1 {timestamp} web01 toktok {} DOWN \
+                     [toktok@32473 service=\"db \\\"main\\\"\" event=\"down\" status=\"Error\"] \
+                     Service db \"main\" is down with status Error: Refused",
+                    std::process::id()
+                )
+            );
+        }
+    }
+}
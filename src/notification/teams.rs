@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::{Value, json};
+use yaml_rust2::Yaml;
+
+use crate::{
+    checker::structs::CheckerStatus,
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        http,
+    },
+    parser::ConfigKey,
+};
+
+/// Sends the notification to a Microsoft Teams webhook as an Adaptive Card.
+#[derive(Debug, Clone)]
+pub struct TeamsNotifier {
+    client: Client,
+    webhook_url: Url,
+}
+
+impl TeamsNotifier {
+    fn color(status: &CheckerStatus) -> &'static str {
+        match status {
+            CheckerStatus::Success => "good",
+            CheckerStatus::Error => "attention",
+            CheckerStatus::Timeout => "warning",
+        }
+    }
+
+    fn payload(&self, notification: &Notification) -> Value {
+        let exec_result = &notification.result;
+        let checked_at = exec_result.checked_at.strftime("%Y-%m-%d %H:%M:%S %Z");
+        let mut facts = vec![
+            json!({ "title": "Service", "value": exec_result.service_name }),
+            json!({ "title": "Status", "value": exec_result.status.to_string() }),
+            json!({ "title": "Checked at", "value": checked_at.to_string() }),
+        ];
+        if let Some(outage) = notification.outage() {
+            facts.push(json!({ "title": "Outage", "value": format!("{outage:#}") }));
+        }
+
+        json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": [
+                        {
+                            "type": "TextBlock",
+                            "text": notification.title(),
+                            "size": "Medium",
+                            "weight": "Bolder",
+                            "color": TeamsNotifier::color(&exec_result.status),
+                            "wrap": true,
+                        },
+                        { "type": "FactSet", "facts": facts },
+                        { "type": "TextBlock", "text": exec_result.message, "wrap": true },
+                    ],
+                },
+            }],
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for TeamsNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let request = self
+            .client
+            .post(self.webhook_url.clone())
+            .json(&self.payload(notification));
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for TeamsNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        Ok(TeamsNotifier {
+            client: http::client(data)?,
+            webhook_url: http::url(data, ConfigKey::WebhookUrl, NotificationType::Teams)?,
+        })
+    }
+}
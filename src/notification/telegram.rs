@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::json;
+use tracing::{Level, event};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        fields, http,
+    },
+    parser::ConfigKey,
+};
+
+const DEFAULT_API_URL: &str = "https://api.telegram.org";
+
+/// Sends the notification to a list of chats through the `sendMessage` method of the Bot API.
+/// The bot token is part of the URL, so it's removed from the logged errors.
+#[derive(Debug, Clone)]
+pub struct TelegramNotifier {
+    client: Client,
+    send_message_url: Url,
+    chat_ids: Vec<String>,
+}
+
+impl TelegramNotifier {
+    /// Escape all the characters reserved by the MarkdownV2 parse mode.
+    fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if "_*[]()~`>#+-=|{}.!\\".contains(c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    fn text(notification: &Notification) -> String {
+        let exec_result = &notification.result;
+        let checked_at = exec_result.checked_at.strftime("%Y-%m-%d %H:%M:%S %Z");
+        let mut lines = vec![
+            format!("*{}*", TelegramNotifier::escape(&notification.title())),
+            format!(
+                "*Service:* {}",
+                TelegramNotifier::escape(&exec_result.service_name)
+            ),
+            format!(
+                "*Status:* {}",
+                TelegramNotifier::escape(&exec_result.status.to_string())
+            ),
+            format!(
+                "*Checked at:* {}",
+                TelegramNotifier::escape(&checked_at.to_string())
+            ),
+        ];
+        if let Some(outage) = notification.outage() {
+            lines.push(format!(
+                "*Outage:* {}",
+                TelegramNotifier::escape(&format!("{outage:#}"))
+            ));
+        }
+        lines.push(format!(
+            "*Message:* {}",
+            TelegramNotifier::escape(&exec_result.message)
+        ));
+        lines.join("\n")
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    /// The message is sent to every chat, even when some of them fail.
+    /// A failure of any of them fails the notification, preferring an error which
+    /// a new attempt may solve, as a missed alert is worse than a repeated one.
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let text = TelegramNotifier::text(notification);
+        let mut error: Option<NotificationError> = None;
+        for chat_id in &self.chat_ids {
+            let request = self
+                .client
+                .post(self.send_message_url.clone())
+                .json(&json!({
+                    "chat_id": chat_id,
+                    "text": text,
+                    "parse_mode": "MarkdownV2",
+                }));
+
+            if let Err(err) = http::send(request).await {
+                event!(
+                    Level::WARN,
+                    chat_id,
+                    error = %err,
+                    "Error sending the Telegram notification to a chat"
+                );
+                if error.as_ref().is_none_or(|error| !error.is_retryable()) {
+                    error = Some(err);
+                }
+            }
+        }
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl TryFrom<&Yaml> for TelegramNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let bot_token =
+            fields::secret_file(data, ConfigKey::BotTokenFile, NotificationType::Telegram)?;
+        let chat_ids = fields::required_list(data, ConfigKey::ChatIds, NotificationType::Telegram)?;
+        let api_url = fields::optional_str(data, ConfigKey::ApiUrl)?
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+
+        let send_message_url = Url::parse(&format!(
+            "{}/bot{bot_token}/sendMessage",
+            api_url.trim_end_matches('/')
+        ))
+        .map_err(|e| {
+            NotificationParseError::InternalParse(format!(
+                "The telegram '{}' is not a valid URL: {e}",
+                ConfigKey::ApiUrl
+            ))
+        })?;
+
+        Ok(TelegramNotifier {
+            client: http::client(data)?,
+            send_message_url,
+            chat_ids,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::notification::testing::{Listener, notification, secret_file, section};
+
+    #[test]
+    fn escape_markdown_v2() {
+        let cases = [
+            ("db.main", "db\\.main"),
+            ("api-v2 (eu)", "api\\-v2 \\(eu\\)"),
+            (
+                "_*[]()~`>#+-=|{}.!\\",
+                "\\_\\*\\[\\]\\(\\)\\~\\`\\>\\#\\+\\-\\=\\|\\{\\}\\.\\!\\\\",
+            ),
+            (
+                "status 500: 'bad' <gateway>",
+                "status 500: 'bad' <gateway\\>",
+            ),
+            ("café", "café"),
+        ];
+        for (text, escaped) in cases {
+            assert_eq!(TelegramNotifier::escape(text), escaped, "escape of {text}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_the_message_to_every_chat() {
+        let mut listener = Listener::start(200).await;
+        let token = secret_file("telegram.token", "123:ABC\n");
+        let notifier = TelegramNotifier::try_from(&section(&format!(
+            "bot_token_file: {token}\nchat_ids: [-1001, '@ops']\napi_url: {}/",
+            listener.url
+        )))
+        .unwrap();
+        notifier
+            .notify(&notification("db.main", "Timed out (5s)"))
+            .await
+            .unwrap();
+
+        let requests = listener.requests();
+        let chat_ids: Vec<Value> = requests
+            .iter()
+            .map(|request| request.json()["chat_id"].clone())
+            .collect();
+        assert_eq!(chat_ids, [json!("-1001"), json!("@ops")]);
+        for request in &requests {
+            assert_eq!(request.path, "/bot123:ABC/sendMessage");
+            assert_eq!(request.json()["parse_mode"], "MarkdownV2");
+            assert_eq!(
+                request.json()["text"],
+                "*Service db\\.main is down with status Error*\n\
+                 *Service:* db\\.main\n\
+                 *Status:* Error\n\
+                 *Checked at:* 2026\\-01\\-02 03:04:05 UTC\n\
+                 *Message:* Timed out \\(5s\\)"
+            );
+        }
+    }
+}
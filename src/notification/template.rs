@@ -0,0 +1,96 @@
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{Notification, error::NotificationParseError, fields},
+    parser::ConfigKey,
+};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// A text with `{{placeholder}}` markers, replaced by the values of a notification.
+/// The placeholders are `service`, `status`, `message`, `event`, `title`, `intro`,
+/// `checked_at`, `down_since`, `outage` and `tags`, any other marker is kept as it is.
+#[derive(Debug, Clone)]
+pub struct Template(String);
+
+impl Template {
+    pub fn new(text: &str) -> Self {
+        Self(text.to_string())
+    }
+
+    /// Read the optional template file which path is at `key`.
+    pub fn from_file(data: &Yaml, key: ConfigKey) -> Result<Option<Self>, NotificationParseError> {
+        Ok(fields::optional_file(data, key)?.map(Self))
+    }
+
+    pub fn render(&self, notification: &Notification) -> String {
+        self.render_with(notification, str::to_string)
+    }
+
+    /// Render the template escaping the values, as they can't be trusted inside a HTML document.
+    pub fn render_html(&self, notification: &Notification) -> String {
+        self.render_with(notification, Template::escape_html)
+    }
+
+    fn render_with(&self, notification: &Notification, escape: fn(&str) -> String) -> String {
+        let mut rendered = String::with_capacity(self.0.len());
+        let mut rest = self.0.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}").map(|end| start + end + 2) else {
+                break;
+            };
+
+            rendered.push_str(&rest[..start]);
+            match Template::value(rest[start + 2..end - 2].trim(), notification) {
+                Some(value) => rendered.push_str(&escape(&value)),
+                None => rendered.push_str(&rest[start..end]),
+            }
+            rest = &rest[end..];
+        }
+        rendered.push_str(rest);
+
+        rendered
+    }
+
+    fn value(placeholder: &str, notification: &Notification) -> Option<String> {
+        let exec_result = &notification.result;
+        let value = match placeholder {
+            "service" => exec_result.service_name.clone(),
+            "status" => exec_result.status.to_string(),
+            "message" => exec_result.message.clone(),
+            "event" => notification.kind.to_string(),
+            "title" => notification.title(),
+            "intro" => notification.intro(),
+            "checked_at" => exec_result.checked_at.strftime(DATE_FORMAT).to_string(),
+            "down_since" => notification
+                .down_since()
+                .map(|down_since| down_since.strftime(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            "outage" => notification
+                .outage()
+                .map(|outage| format!("{outage:#}"))
+                .unwrap_or_default(),
+            "tags" => notification.tags.join(", "),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Escape the characters reserved in a HTML text or attribute value.
+    pub fn escape_html(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _ => escaped.push(c),
+            }
+        }
+
+        escaped
+    }
+}
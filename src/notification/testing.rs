@@ -0,0 +1,149 @@
+use jiff::Zoned;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+};
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{
+    checker::structs::{CheckerResult, CheckerStatus},
+    notification::{Notification, NotificationKind},
+};
+
+/// A HTTP request received by the `Listener`.
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap()
+    }
+
+    /// The value of `name` in a form encoded body.
+    pub fn form(&self, name: &str) -> Option<String> {
+        reqwest::Url::parse(&format!("http://localhost/?{}", self.body))
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// A local HTTP server, standing in for the API of a notifier.
+/// It answers every request with `status` and keeps them to be checked by the test.
+pub struct Listener {
+    pub url: String,
+    requests: UnboundedReceiver<Request>,
+}
+
+impl Listener {
+    pub async fn start(status: u16) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, requests) = unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Listener::answer(stream, status, tx.clone()));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    async fn answer(mut stream: TcpStream, status: u16, tx: UnboundedSender<Request>) {
+        let mut received = vec![];
+        let mut buffer = [0; 4096];
+        let head_end = loop {
+            if let Some(end) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+                break end;
+            }
+            match stream.read(&mut buffer).await {
+                Ok(0) | Err(_) => return,
+                Ok(read) => received.extend_from_slice(&buffer[..read]),
+            }
+        };
+
+        let head = String::from_utf8_lossy(&received[..head_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let method = request_line.next().unwrap_or_default().to_string();
+        let path = request_line.next().unwrap_or_default().to_string();
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or_default();
+        let mut body = received[head_end + 4..].to_vec();
+        while body.len() < length {
+            match stream.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => body.extend_from_slice(&buffer[..read]),
+            }
+        }
+
+        let _ = tx.send(Request {
+            method,
+            path,
+            headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+        });
+        let response =
+            format!("HTTP/1.1 {status} Test\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+        let _ = stream.write_all(response.as_bytes()).await;
+    }
+
+    /// The requests received so far, in the order they arrived.
+    pub fn requests(&mut self) -> Vec<Request> {
+        let mut requests = vec![];
+        while let Ok(request) = self.requests.try_recv() {
+            requests.push(request);
+        }
+        requests
+    }
+}
+
+/// Parse the YAML of a notification section.
+pub fn section(yaml: &str) -> Yaml {
+    YamlLoader::load_from_str(yaml).unwrap().remove(0)
+}
+
+/// Write a secret file for the notifiers reading their credentials from one, returning its path.
+pub fn secret_file(name: &str, secret: &str) -> String {
+    let dir = std::env::temp_dir().join("toktok-tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("{name}-{}", std::process::id()));
+    std::fs::write(&path, secret).unwrap();
+    path.display().to_string()
+}
+
+/// A failure notification of `service_name`, checked at a fixed time.
+pub fn notification(service_name: &str, message: &str) -> Notification {
+    let result = CheckerResult {
+        checked_at: "2026-01-02T03:04:05Z[UTC]".parse::<Zoned>().unwrap(),
+        ..CheckerResult::new(
+            service_name.to_string(),
+            CheckerStatus::Error,
+            message.to_string(),
+        )
+    };
+    Notification::new(result, NotificationKind::Down, vec!["db".to_string()], None)
+}
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use reqwest::{Client, Method, Url, header::HeaderMap};
+use yaml_rust2::Yaml;
+
+use crate::{
+    notification::{
+        Notification, NotificationType, Notifier,
+        error::{NotificationError, NotificationParseError},
+        http,
+    },
+    parser::ConfigKey,
+};
+
+/// Sends the notification as a JSON payload to any HTTP endpoint.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let request = self
+            .client
+            .request(self.method.clone(), self.url.clone())
+            .headers(self.headers.clone())
+            .json(&notification.payload());
+
+        http::send(request).await
+    }
+}
+
+impl TryFrom<&Yaml> for WebhookNotifier {
+    type Error = NotificationParseError;
+
+    fn try_from(data: &Yaml) -> Result<Self, Self::Error> {
+        let url = http::url(data, ConfigKey::Url, NotificationType::Webhook)?;
+
+        let method = match &data[ConfigKey::Method.as_ref()] {
+            Yaml::String(method) => Method::from_str(&method.to_uppercase()).map_err(|_| {
+                NotificationParseError::InvalidFormat(
+                    ConfigKey::Method,
+                    "a HTTP method like POST or PUT".to_string(),
+                )
+            })?,
+            Yaml::BadValue => Method::POST,
+            _ => {
+                return Err(NotificationParseError::InvalidFormat(
+                    ConfigKey::Method,
+                    "a HTTP method like POST or PUT".to_string(),
+                ));
+            }
+        };
+
+        Ok(WebhookNotifier {
+            client: http::client(data)?,
+            method,
+            url,
+            headers: http::headers(data)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::testing::{Listener, notification, section};
+
+    #[tokio::test]
+    async fn sends_the_payload() {
+        let mut listener = Listener::start(200).await;
+        let notifier = WebhookNotifier::try_from(&section(&format!(
+            "url: {}/hooks/toktok\nmethod: put\nheaders:\n  X-Token: secret",
+            listener.url
+        )))
+        .unwrap();
+        notifier
+            .notify(&notification("db.main", "Connection refused"))
+            .await
+            .unwrap();
+
+        let requests = listener.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/hooks/toktok");
+        assert_eq!(request.header("x-token"), Some("secret"));
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(
+            request.json(),
+            serde_json::json!({
+                "service": "db.main",
+                "event": "down",
+                "status": "Error",
+                "message": "Connection refused",
+                "checked_at": "2026-01-02T03:04:05Z",
+                "down_since": null,
+                "outage_seconds": null,
+                "escalation_tier": null,
+                "tags": ["db"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_request_is_an_error() {
+        let listener = Listener::start(404).await;
+        let notifier =
+            WebhookNotifier::try_from(&section(&format!("url: {}/missing", listener.url))).unwrap();
+        let err = notifier
+            .notify(&notification("db.main", "Connection refused"))
+            .await
+            .unwrap_err();
+        assert!(!err.is_retryable());
+    }
+}
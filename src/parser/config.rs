@@ -1,34 +1,43 @@
-use std::io::Read;
+use std::{io::Read, str::FromStr};
 
 use jiff::SignedDuration;
 use yaml_rust2::{Yaml, YamlLoader};
 
 use crate::{
     args::Args,
-    checker::{error::CheckerParseError, Checker},
-    notification::{email::MailNotifier, error::NotificationParseError},
+    checker::{Checker, error::CheckerParseError},
+    notification::{
+        NamedNotifier, NotificationType, digest::DigestPolicy, error::NotificationParseError,
+        outbox::OutboxPolicy,
+    },
     parser::{
-        error::{ConfigFileError, ConfigParseError}, ConfigKey
+        ConfigKey,
+        error::{ConfigFileError, ConfigParseError},
+        keys::ConfigKeyInvalidFormat,
     },
     task::Task,
-    task_info::TaskInfo,
+    task_info::{EscalationTier, RetryPolicy, TaskInfo},
 };
 
 const DEFAULT_CONFIG_FILE: &str = "toktok.yaml";
 
 /// The general struct for the config file after all validations.
-/// Each new notification method is added as a field
+/// Each notifier is named by its key inside the `notification` section.
 #[derive(Default)]
 pub struct Configuration {
     pub tasks: Vec<Task>,
-    pub mailer: Option<MailNotifier>,
+    pub notifiers: Vec<NamedNotifier>,
+    pub outbox: OutboxPolicy,
+    pub digest: Option<DigestPolicy>,
 }
 
 /// Responsible by the build proccess while reading the config file.
 pub struct ConfigurationBuilder {
     config: Vec<Yaml>,
     tasks: Vec<Task>,
-    mailer: Option<MailNotifier>,
+    notifiers: Vec<NamedNotifier>,
+    outbox: OutboxPolicy,
+    digest: Option<DigestPolicy>,
 }
 impl ConfigurationBuilder {
     /// Create the base builder, this point also already read the config file,
@@ -37,7 +46,9 @@ impl ConfigurationBuilder {
         Ok(Self {
             config: ConfigurationBuilder::load_config(args.config.as_deref())?,
             tasks: vec![],
-            mailer: None,
+            notifiers: vec![],
+            outbox: OutboxPolicy::default(),
+            digest: None,
         })
     }
 
@@ -64,8 +75,19 @@ impl ConfigurationBuilder {
                     let service_name = service.0.as_str().unwrap().to_string();
 
                     let interval = self.interval(service.1)?;
+                    let retry_policy = self.retry_policy(service.1)?;
+                    let tags = self.tags(service.1)?;
+                    let notifiers = self.service_notifiers(service.1)?;
+                    let escalation = self.escalation(service.1)?;
                     let checker = self.get_checker(service.1)?;
-                    let info = TaskInfo::new(service_name, interval);
+                    let info = TaskInfo::new(
+                        service_name,
+                        interval,
+                        retry_policy,
+                        tags,
+                        notifiers,
+                        escalation,
+                    );
 
                     tasks.push(Task::new(info, checker));
                 }
@@ -97,14 +119,205 @@ impl ConfigurationBuilder {
         }
     }
 
-    /// Parse the specific `notification->mailer` map into the config file.
-    pub fn mailer(mut self) -> Result<Self, NotificationParseError> {
-        let mailer_section = &self.config[0]["notification"]["mailer"];
-        if !mailer_section.is_hash() {
-            return Ok(self);
+    /// Read the optional `retries`, `retry_interval` and `failure_threshold` of a service,
+    /// falling back to the defaults of `RetryPolicy` when absent.
+    fn retry_policy(&self, service_attrs: &Yaml) -> Result<RetryPolicy, CheckerParseError> {
+        let mut retry_policy = RetryPolicy::default();
+
+        if let Some(retries) = self.optional_count(service_attrs, ConfigKey::Retries, 0)? {
+            retry_policy.retries = retries;
+        }
+        if let Some(retry_interval) =
+            self.optional_count(service_attrs, ConfigKey::RetryInterval, 1)?
+        {
+            retry_policy.retry_interval = SignedDuration::from_secs(retry_interval.into());
+        }
+        if let Some(threshold) =
+            self.optional_count(service_attrs, ConfigKey::FailureThreshold, 1)?
+        {
+            retry_policy.failure_threshold = threshold;
+        }
+
+        Ok(retry_policy)
+    }
+
+    /// Read the optional `tags` list of a service, used by notifiers to classify alerts.
+    fn tags(&self, service_attrs: &Yaml) -> Result<Vec<String>, CheckerParseError> {
+        match &service_attrs[ConfigKey::Tags.as_ref()] {
+            Yaml::BadValue => Ok(vec![]),
+            Yaml::Array(tags) if tags.iter().all(|tag| tag.as_str().is_some()) => Ok(tags
+                .iter()
+                .map(|tag| tag.as_str().unwrap().trim().to_string())
+                .collect()),
+            _ => Err(CheckerParseError::InvalidFormat(
+                ConfigKey::Tags,
+                ConfigKeyInvalidFormat::new(ConfigKey::Tags),
+            )),
+        }
+    }
+
+    /// Read the optional `notifiers` list of a service, the names of the notifiers it alerts.
+    /// When absent, all the notifiers are alerted.
+    fn service_notifiers(
+        &self,
+        service_attrs: &Yaml,
+    ) -> Result<Option<Vec<String>>, CheckerParseError> {
+        match &service_attrs[ConfigKey::Notifiers.as_ref()] {
+            Yaml::BadValue => Ok(None),
+            Yaml::Array(names) if names.iter().all(|name| name.as_str().is_some()) => Ok(Some(
+                names
+                    .iter()
+                    .map(|name| name.as_str().unwrap().trim().to_string())
+                    .collect(),
+            )),
+            _ => Err(CheckerParseError::InvalidFormat(
+                ConfigKey::Notifiers,
+                ConfigKeyInvalidFormat::new(ConfigKey::Notifiers),
+            )),
+        }
+    }
+
+    /// Read the optional `escalation` list of a service, each tier with its `notifiers`
+    /// and when it's reached, `after_minutes` down or `after_failures` consecutive failures.
+    fn escalation(&self, service_attrs: &Yaml) -> Result<Vec<EscalationTier>, CheckerParseError> {
+        let invalid_format = || {
+            CheckerParseError::InvalidFormat(
+                ConfigKey::Escalation,
+                ConfigKeyInvalidFormat::new(ConfigKey::Escalation),
+            )
+        };
+
+        let tiers = match &service_attrs[ConfigKey::Escalation.as_ref()] {
+            Yaml::BadValue => return Ok(vec![]),
+            Yaml::Array(tiers) => tiers,
+            _ => return Err(invalid_format()),
+        };
+
+        let mut escalation = vec![];
+        for tier in tiers {
+            if !tier.is_hash() {
+                return Err(invalid_format());
+            }
+            let after = self
+                .optional_count(tier, ConfigKey::AfterMinutes, 1)?
+                .map(|minutes| SignedDuration::from_mins(minutes.into()));
+            let after_failures = self.optional_count(tier, ConfigKey::AfterFailures, 1)?;
+            let notifiers = match self.service_notifiers(tier)? {
+                Some(notifiers) if !notifiers.is_empty() => notifiers,
+                _ => return Err(invalid_format()),
+            };
+            if after.is_none() && after_failures.is_none() {
+                return Err(invalid_format());
+            }
+
+            escalation.push(EscalationTier {
+                after,
+                after_failures,
+                notifiers,
+            });
+        }
+
+        Ok(escalation)
+    }
+
+    fn optional_count(
+        &self,
+        service_attrs: &Yaml,
+        key: ConfigKey,
+        min: u32,
+    ) -> Result<Option<u32>, CheckerParseError> {
+        match &service_attrs[key.as_ref()] {
+            Yaml::BadValue => Ok(None),
+            Yaml::Integer(count) => match u32::try_from(*count) {
+                Ok(count) if count >= min => Ok(Some(count)),
+                _ => Err(CheckerParseError::InvalidFormat(
+                    key,
+                    ConfigKeyInvalidFormat::new(key),
+                )),
+            },
+            _ => Err(CheckerParseError::InvalidFormat(
+                key,
+                ConfigKeyInvalidFormat::new(key),
+            )),
+        }
+    }
+
+    /// Parse each section of the `notification` map into a named notifier.
+    /// The type of the notifier is read from the `type` key of the section,
+    /// when absent the section name is the type, like `mailer` or `slack`.
+    pub fn notifiers(mut self) -> Result<Self, NotificationParseError> {
+        let sections = match &self.config[0][ConfigKey::Notification.as_ref()] {
+            Yaml::Hash(sections) => sections,
+            Yaml::BadValue => return Ok(self),
+            _ => {
+                return Err(NotificationParseError::InternalParse(format!(
+                    "Key '{}' must have a YAML Map format",
+                    ConfigKey::Notification
+                )));
+            }
+        };
+
+        let mut notifiers = vec![];
+        for (name, section) in sections.iter() {
+            let Some(name) = name.as_str() else {
+                return Err(NotificationParseError::InternalParse(
+                    "The notifier names must be strings".to_string(),
+                ));
+            };
+            if !section.is_hash() {
+                return Err(NotificationParseError::InternalParse(format!(
+                    "The notifier '{name}' must have a YAML Map format"
+                )));
+            }
+
+            let notification_type = match &section[ConfigKey::Type.as_ref()] {
+                Yaml::String(notification_type) => notification_type.as_str(),
+                _ => name,
+            };
+            let notification_type = NotificationType::from_str(notification_type)
+                .map_err(|e| NotificationParseError::InvalidType(name.to_string(), e))?;
+
+            notifiers.push(NamedNotifier::new(
+                name.to_string(),
+                notification_type.notifier(section)?,
+            ));
+        }
+
+        self.notifiers = notifiers;
+        Ok(self)
+    }
+
+    /// Parse the optional `outbox` map, which defines how the failed notifications are retried.
+    pub fn outbox(mut self) -> Result<Self, NotificationParseError> {
+        let outbox_section = &self.config[0][ConfigKey::Outbox.as_ref()];
+        match outbox_section {
+            Yaml::Hash(_) => self.outbox = OutboxPolicy::try_from(outbox_section)?,
+            Yaml::BadValue => {}
+            _ => {
+                return Err(NotificationParseError::InternalParse(format!(
+                    "Key '{}' must have a YAML Map format",
+                    ConfigKey::Outbox
+                )));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Parse the optional `digest` map, without it every notification is sent on its own.
+    pub fn digest(mut self) -> Result<Self, NotificationParseError> {
+        let digest_section = &self.config[0][ConfigKey::Digest.as_ref()];
+        match digest_section {
+            Yaml::Hash(_) => self.digest = Some(DigestPolicy::try_from(digest_section)?),
+            Yaml::BadValue => {}
+            _ => {
+                return Err(NotificationParseError::InternalParse(format!(
+                    "Key '{}' must have a YAML Map format",
+                    ConfigKey::Digest
+                )));
+            }
         }
 
-        self.mailer = Some(MailNotifier::try_from(mailer_section)?);
         Ok(self)
     }
 
@@ -114,9 +327,67 @@ impl ConfigurationBuilder {
             return Err(ConfigParseError::NoServiceProvided);
         }
 
+        let mut tasks = self.tasks;
+        for task in &mut tasks {
+            let escalation_names: Vec<&String> = task
+                .escalation()
+                .iter()
+                .flat_map(|tier| &tier.notifiers)
+                .collect();
+            if let Some(unknown) = task
+                .notifiers()
+                .into_iter()
+                .flatten()
+                .chain(escalation_names.iter().copied())
+                .find(|name| !self.notifiers.iter().any(|n| &n.name == *name))
+            {
+                return Err(ConfigParseError::UnknownNotifier(
+                    task.name(),
+                    unknown.to_string(),
+                ));
+            }
+
+            // Without its own list, a service with escalation alerts first
+            // all the notifiers which are not part of a tier.
+            if task.notifiers().is_none() && !escalation_names.is_empty() {
+                let notifiers: Vec<String> = self
+                    .notifiers
+                    .iter()
+                    .map(|n| n.name.clone())
+                    .filter(|name| !escalation_names.contains(&name))
+                    .collect();
+                if notifiers.is_empty() {
+                    return Err(ConfigParseError::NoFirstNotifier(task.name()));
+                }
+                task.set_notifiers(notifiers);
+            }
+        }
+
+        let services: Vec<String> = tasks.iter().map(|task| task.name()).collect();
+        for named in &self.notifiers {
+            let routed: Vec<String> = tasks
+                .iter()
+                .filter(|task| {
+                    task.notifiers()
+                        .is_none_or(|names| names.contains(&named.name))
+                        || task
+                            .escalation()
+                            .iter()
+                            .any(|tier| tier.notifiers.contains(&named.name))
+                })
+                .map(|task| task.name())
+                .collect();
+            named
+                .notifier
+                .check_services(&routed, &services)
+                .map_err(|e| ConfigParseError::MisconfiguredNotifier(named.name.clone(), e))?;
+        }
+
         Ok(Configuration {
-            tasks: self.tasks,
-            mailer: self.mailer,
+            tasks,
+            notifiers: self.notifiers,
+            outbox: self.outbox,
+            digest: self.digest,
         })
     }
 }
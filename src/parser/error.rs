@@ -36,6 +36,9 @@ impl ConfigError for ConfigFileError {}
 #[derive(Debug)]
 pub enum ConfigParseError {
     NoServiceProvided,
+    UnknownNotifier(String, String),
+    MisconfiguredNotifier(String, String),
+    NoFirstNotifier(String),
 }
 impl Display for ConfigParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -43,6 +46,22 @@ impl Display for ConfigParseError {
             ConfigParseError::NoServiceProvided => {
                 write!(f, "None service provided, aborting.")
             }
+            ConfigParseError::UnknownNotifier(service, notifier) => {
+                write!(
+                    f,
+                    "The service '{service}' uses the notifier '{notifier}', which is not defined."
+                )
+            }
+            ConfigParseError::NoFirstNotifier(service) => {
+                write!(
+                    f,
+                    "The service '{service}' has all the notifiers in its escalation tiers, \
+                    its 'notifiers' must list the ones alerted first."
+                )
+            }
+            ConfigParseError::MisconfiguredNotifier(notifier, reason) => {
+                write!(f, "The notifier '{notifier}' is misconfigured: {reason}")
+            }
         }
     }
 }
@@ -1,29 +1,105 @@
 use core::fmt;
 use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum ConfigKey {
     // General service
     Service,
     Interval,
     Timeout,
     Configuration,
+    Retries,
+    RetryInterval,
+    FailureThreshold,
+    Tags,
+    Notifiers,
+    Escalation,
+    AfterMinutes,
+    AfterFailures,
     Type,
     // Service type web
     Url,
     ExpectedHttpCode,
     Headers,
+    BodyContains,
+    BodyNotContains,
+    BodyRegex,
+    JsonAssertions,
+    Body,
+    BodyFile,
+    BasicAuth,
+    PasswordFile,
+    BearerTokenFile,
     // Service type server
     Socket,
     // General notification
     Notification,
+    Outbox,
+    Backoff,
+    MaxBackoff,
+    MaxAge,
+    Digest,
+    Window,
+    RateLimit,
+    RatePeriod,
     // Notification email
     SmtpDomain,
     SmtpCredentials,
+    SmtpPort,
+    Tls,
+    TlsCaFile,
+    HeloName,
     MailFrom,
     MailTo,
     MailCc,
     MailBcc,
+    SubjectTemplate,
+    BodyTemplate,
+    TextTemplate,
+    // Notification webhook
+    Method,
+    // Notification slack
+    WebhookUrl,
+    Channel,
+    Username,
+    Icon,
+    // Notification discord
+    AvatarUrl,
+    // Notification telegram
+    BotTokenFile,
+    ChatIds,
+    ApiUrl,
+    // Notification pagerduty
+    RoutingKeyFile,
+    ServiceRoutingKeyFiles,
+    // Notification opsgenie
+    ApiKeyFile,
+    // Notification command
+    Path,
+    Args,
+    // Notification push
+    TopicUrl,
+    TokenFile,
+    Priority,
+    ServerUrl,
+    AppTokenFile,
+    UserKeyFile,
+    // Notification matrix
+    HomeserverUrl,
+    RoomId,
+    AccessTokenFile,
+    // Notification sms
+    AccountSid,
+    AuthTokenFile,
+    SmsFrom,
+    SmsTo,
+    // Notification syslog
+    Transport,
+    Address,
+    Facility,
+    Severity,
+    Hostname,
+    AppName,
 }
 impl AsRef<str> for ConfigKey {
     fn as_ref(&self) -> &str {
@@ -33,17 +109,82 @@ impl AsRef<str> for ConfigKey {
             ConfigKey::Url => "url",
             ConfigKey::ExpectedHttpCode => "expected_http_code",
             ConfigKey::Headers => "headers",
+            ConfigKey::BodyContains => "body_contains",
+            ConfigKey::BodyNotContains => "body_not_contains",
+            ConfigKey::BodyRegex => "body_regex",
+            ConfigKey::JsonAssertions => "json_assertions",
+            ConfigKey::Body => "body",
+            ConfigKey::BodyFile => "body_file",
+            ConfigKey::BasicAuth => "basic_auth",
+            ConfigKey::PasswordFile => "password_file",
+            ConfigKey::BearerTokenFile => "bearer_token_file",
             ConfigKey::Timeout => "timeout",
             ConfigKey::Configuration => "configuration",
+            ConfigKey::Retries => "retries",
+            ConfigKey::RetryInterval => "retry_interval",
+            ConfigKey::FailureThreshold => "failure_threshold",
+            ConfigKey::Tags => "tags",
+            ConfigKey::Notifiers => "notifiers",
+            ConfigKey::Escalation => "escalation",
+            ConfigKey::AfterMinutes => "after_minutes",
+            ConfigKey::AfterFailures => "after_failures",
             ConfigKey::Type => "type",
             ConfigKey::Socket => "socket",
             ConfigKey::Notification => "notification",
+            ConfigKey::Outbox => "outbox",
+            ConfigKey::Backoff => "backoff",
+            ConfigKey::MaxBackoff => "max_backoff",
+            ConfigKey::MaxAge => "max_age",
+            ConfigKey::Digest => "digest",
+            ConfigKey::Window => "window",
+            ConfigKey::RateLimit => "rate_limit",
+            ConfigKey::RatePeriod => "rate_period",
             ConfigKey::SmtpDomain => "smtp_domain",
             ConfigKey::SmtpCredentials => "smtp_credentials",
+            ConfigKey::SmtpPort => "smtp_port",
+            ConfigKey::Tls => "tls",
+            ConfigKey::TlsCaFile => "tls_ca_file",
+            ConfigKey::HeloName => "helo_name",
             ConfigKey::MailFrom => "from",
             ConfigKey::MailTo => "to",
             ConfigKey::MailCc => "cc",
             ConfigKey::MailBcc => "bcc",
+            ConfigKey::SubjectTemplate => "subject_template",
+            ConfigKey::BodyTemplate => "body_template",
+            ConfigKey::TextTemplate => "text_template",
+            ConfigKey::Method => "method",
+            ConfigKey::WebhookUrl => "webhook_url",
+            ConfigKey::Channel => "channel",
+            ConfigKey::Username => "username",
+            ConfigKey::Icon => "icon",
+            ConfigKey::AvatarUrl => "avatar_url",
+            ConfigKey::BotTokenFile => "bot_token_file",
+            ConfigKey::ChatIds => "chat_ids",
+            ConfigKey::ApiUrl => "api_url",
+            ConfigKey::RoutingKeyFile => "routing_key_file",
+            ConfigKey::ServiceRoutingKeyFiles => "service_routing_key_files",
+            ConfigKey::ApiKeyFile => "api_key_file",
+            ConfigKey::Path => "path",
+            ConfigKey::Args => "args",
+            ConfigKey::TopicUrl => "topic_url",
+            ConfigKey::TokenFile => "token_file",
+            ConfigKey::Priority => "priority",
+            ConfigKey::ServerUrl => "server_url",
+            ConfigKey::AppTokenFile => "app_token_file",
+            ConfigKey::UserKeyFile => "user_key_file",
+            ConfigKey::HomeserverUrl => "homeserver_url",
+            ConfigKey::RoomId => "room_id",
+            ConfigKey::AccessTokenFile => "access_token_file",
+            ConfigKey::AccountSid => "account_sid",
+            ConfigKey::AuthTokenFile => "auth_token_file",
+            ConfigKey::SmsFrom => "from",
+            ConfigKey::SmsTo => "to",
+            ConfigKey::Transport => "transport",
+            ConfigKey::Address => "address",
+            ConfigKey::Facility => "facility",
+            ConfigKey::Severity => "severity",
+            ConfigKey::Hostname => "hostname",
+            ConfigKey::AppName => "app_name",
         }
     }
 }
@@ -55,17 +196,82 @@ impl Display for ConfigKey {
             ConfigKey::Url => write!(f, "url"),
             ConfigKey::ExpectedHttpCode => write!(f, "expected_http_code"),
             ConfigKey::Headers => write!(f, "headers"),
+            ConfigKey::BodyContains => write!(f, "body_contains"),
+            ConfigKey::BodyNotContains => write!(f, "body_not_contains"),
+            ConfigKey::BodyRegex => write!(f, "body_regex"),
+            ConfigKey::JsonAssertions => write!(f, "json_assertions"),
+            ConfigKey::Body => write!(f, "body"),
+            ConfigKey::BodyFile => write!(f, "body_file"),
+            ConfigKey::BasicAuth => write!(f, "basic_auth"),
+            ConfigKey::PasswordFile => write!(f, "password_file"),
+            ConfigKey::BearerTokenFile => write!(f, "bearer_token_file"),
             ConfigKey::Timeout => write!(f, "timeout"),
             ConfigKey::Configuration => write!(f, "configuration"),
+            ConfigKey::Retries => write!(f, "retries"),
+            ConfigKey::RetryInterval => write!(f, "retry_interval"),
+            ConfigKey::FailureThreshold => write!(f, "failure_threshold"),
+            ConfigKey::Tags => write!(f, "tags"),
+            ConfigKey::Notifiers => write!(f, "notifiers"),
+            ConfigKey::Escalation => write!(f, "escalation"),
+            ConfigKey::AfterMinutes => write!(f, "after_minutes"),
+            ConfigKey::AfterFailures => write!(f, "after_failures"),
             ConfigKey::Type => write!(f, "type"),
             ConfigKey::Socket => write!(f, "socket"),
             ConfigKey::Notification => write!(f, "notification"),
+            ConfigKey::Outbox => write!(f, "outbox"),
+            ConfigKey::Backoff => write!(f, "backoff"),
+            ConfigKey::MaxBackoff => write!(f, "max_backoff"),
+            ConfigKey::MaxAge => write!(f, "max_age"),
+            ConfigKey::Digest => write!(f, "digest"),
+            ConfigKey::Window => write!(f, "window"),
+            ConfigKey::RateLimit => write!(f, "rate_limit"),
+            ConfigKey::RatePeriod => write!(f, "rate_period"),
             ConfigKey::SmtpDomain => write!(f, "smtp_domain"),
             ConfigKey::SmtpCredentials => write!(f, "smtp_credentials"),
+            ConfigKey::SmtpPort => write!(f, "smtp_port"),
+            ConfigKey::Tls => write!(f, "tls"),
+            ConfigKey::TlsCaFile => write!(f, "tls_ca_file"),
+            ConfigKey::HeloName => write!(f, "helo_name"),
             ConfigKey::MailFrom => write!(f, "from"),
             ConfigKey::MailTo => write!(f, "to"),
             ConfigKey::MailCc => write!(f, "cc"),
             ConfigKey::MailBcc => write!(f, "bcc"),
+            ConfigKey::SubjectTemplate => write!(f, "subject_template"),
+            ConfigKey::BodyTemplate => write!(f, "body_template"),
+            ConfigKey::TextTemplate => write!(f, "text_template"),
+            ConfigKey::Method => write!(f, "method"),
+            ConfigKey::WebhookUrl => write!(f, "webhook_url"),
+            ConfigKey::Channel => write!(f, "channel"),
+            ConfigKey::Username => write!(f, "username"),
+            ConfigKey::Icon => write!(f, "icon"),
+            ConfigKey::AvatarUrl => write!(f, "avatar_url"),
+            ConfigKey::BotTokenFile => write!(f, "bot_token_file"),
+            ConfigKey::ChatIds => write!(f, "chat_ids"),
+            ConfigKey::ApiUrl => write!(f, "api_url"),
+            ConfigKey::RoutingKeyFile => write!(f, "routing_key_file"),
+            ConfigKey::ServiceRoutingKeyFiles => write!(f, "service_routing_key_files"),
+            ConfigKey::ApiKeyFile => write!(f, "api_key_file"),
+            ConfigKey::Path => write!(f, "path"),
+            ConfigKey::Args => write!(f, "args"),
+            ConfigKey::TopicUrl => write!(f, "topic_url"),
+            ConfigKey::TokenFile => write!(f, "token_file"),
+            ConfigKey::Priority => write!(f, "priority"),
+            ConfigKey::ServerUrl => write!(f, "server_url"),
+            ConfigKey::AppTokenFile => write!(f, "app_token_file"),
+            ConfigKey::UserKeyFile => write!(f, "user_key_file"),
+            ConfigKey::HomeserverUrl => write!(f, "homeserver_url"),
+            ConfigKey::RoomId => write!(f, "room_id"),
+            ConfigKey::AccessTokenFile => write!(f, "access_token_file"),
+            ConfigKey::AccountSid => write!(f, "account_sid"),
+            ConfigKey::AuthTokenFile => write!(f, "auth_token_file"),
+            ConfigKey::SmsFrom => write!(f, "from"),
+            ConfigKey::SmsTo => write!(f, "to"),
+            ConfigKey::Transport => write!(f, "transport"),
+            ConfigKey::Address => write!(f, "address"),
+            ConfigKey::Facility => write!(f, "facility"),
+            ConfigKey::Severity => write!(f, "severity"),
+            ConfigKey::Hostname => write!(f, "hostname"),
+            ConfigKey::AppName => write!(f, "app_name"),
         }
     }
 }
@@ -82,8 +288,47 @@ impl ConfigKeyInvalidFormat {
 impl Display for ConfigKeyInvalidFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
-            ConfigKey::ExpectedHttpCode => write!(f, "Must be a valid HTTP Code"),
-            ConfigKey::Timeout => write!(f, "Must be a number greater than zero"),
+            ConfigKey::ExpectedHttpCode => write!(
+                f,
+                "Must be a valid HTTP Code like 200, a range like \"2xx\" or \"200-299\", \
+                or an array of them like [200, \"3xx\"]"
+            ),
+            ConfigKey::Timeout
+            | ConfigKey::RetryInterval
+            | ConfigKey::FailureThreshold
+            | ConfigKey::AfterMinutes
+            | ConfigKey::AfterFailures => write!(f, "Must be a number greater than zero"),
+            ConfigKey::Retries => write!(f, "Must be a number equal or greater than zero"),
+            ConfigKey::Tags | ConfigKey::Notifiers => write!(f, "Must be an array of strings"),
+            ConfigKey::JsonAssertions => write!(
+                f,
+                "Must be an array of assertions like '$.status == \"ok\"' or '$.version exists'"
+            ),
+            ConfigKey::BodyContains | ConfigKey::BodyNotContains => {
+                write!(f, "Must be a string or an array of strings")
+            }
+            ConfigKey::BodyRegex => write!(
+                f,
+                "Must be a valid regular expression or an array of regular expressions"
+            ),
+            ConfigKey::Escalation => write!(
+                f,
+                "Must be an array of tiers, each with '{}' and '{}' or '{}'",
+                ConfigKey::Notifiers,
+                ConfigKey::AfterMinutes,
+                ConfigKey::AfterFailures
+            ),
+            ConfigKey::Method => write!(f, "Must be a HTTP method like GET, HEAD or POST"),
+            ConfigKey::Body
+            | ConfigKey::BodyFile
+            | ConfigKey::PasswordFile
+            | ConfigKey::BearerTokenFile => write!(f, "Must be a non empty string"),
+            ConfigKey::BasicAuth => write!(
+                f,
+                "Must be a map with '{}' and '{}'",
+                ConfigKey::Username,
+                ConfigKey::PasswordFile
+            ),
             ConfigKey::Socket => write!(
                 f,
                 "Must be a value with pattern IP_ADDRESS:PORT or DOMAIN:PORT"
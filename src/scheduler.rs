@@ -1,18 +1,32 @@
 use std::{
-    sync::{Arc, Mutex, mpsc::channel}
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use jiff::Zoned;
+use jiff::{Zoned, civil::DateTime};
+use tokio::{
+    sync::{Notify, mpsc::unbounded_channel},
+    time,
+};
 use tracing::{Level, event};
 
 use crate::{
-    checker::structs::CheckerResult, executor, notification::Notifier, parser::Configuration,
-    queue::PriorityQueue, task::Task,
+    executor,
+    notification::{
+        NamedNotifier, Notification, digest::DigestPolicy, dispatcher::Dispatcher,
+        outbox::OutboxPolicy,
+    },
+    parser::Configuration,
+    queue::PriorityQueue,
+    task::Task,
 };
 
 #[derive(Debug)]
 pub struct Scheduler {
     tasks: Arc<Mutex<PriorityQueue>>,
+    wakeup: Arc<Notify>,
+    outbox: OutboxPolicy,
+    digest: Option<DigestPolicy>,
 }
 
 impl Scheduler {
@@ -25,58 +39,69 @@ impl Scheduler {
             }
         }
 
-        Self { tasks: queue }
+        Self {
+            tasks: queue,
+            wakeup: Arc::new(Notify::new()),
+            outbox: config.outbox,
+            digest: config.digest,
+        }
     }
 
-    /// This method starts 2 threads and start the tasks checker loop.
-    /// First thread will update a task with the calculated next execution time, and enqueue it.
-    /// The second thread is supposed to receive a `CheckerResult` and calls all notifiers to send notifications.
-    /// The tasks loop will check the queue in each iteration searching for tasks which the time of execution reached or passed.
-    /// And the loop spawn a specific thread to the executor validate the service.
-    pub async fn init(&self, notifiers: Vec<impl Notifier + Send + 'static>) {
+    /// This method starts 2 workers and start the tasks checker loop.
+    /// First worker will update a task with the calculated next execution time, enqueue it and wake up the loop.
+    /// The second worker is supposed to receive a `Notification` and dispatch it to the notifiers it's routed to,
+    /// each notifier sends its notifications concurrently with the others.
+    /// The tasks loop dispatches every task which the time of execution reached or passed,
+    /// then sleeps until the next execution time or until a task is enqueued again.
+    /// And the loop spawn a specific task to the executor validate the service.
+    pub async fn init(&self, notifiers: Vec<NamedNotifier>) {
         event!(
             Level::INFO,
             notifiers_count = notifiers.len(),
             "Scheduler has been initiated"
         );
 
-        let (tx_task, rx_task) = channel::<Task>();
-        let (tx_notifier, rx_notifier) = channel::<CheckerResult>();
+        let (tx_task, mut rx_task) = unbounded_channel::<Task>();
+        let (tx_notifier, mut rx_notifier) = unbounded_channel::<Notification>();
         let task_queue = self.tasks.clone();
+        let wakeup = self.wakeup.clone();
         tokio::spawn(async move {
             event!(
                 Level::INFO,
-                "Task update and enqueuer thread has been initiated"
+                "Task update and enqueuer worker has been initiated"
             );
-            loop {
-                let mut task = rx_task.recv().unwrap();
+            while let Some(mut task) = rx_task.recv().await {
                 task.set_next_execution_at();
                 task_queue.lock().unwrap().enqueue(task);
+                wakeup.notify_one();
             }
+            event!(
+                Level::ERROR,
+                "Task channel has been closed, enqueuer stopped"
+            );
         });
 
+        let dispatcher = Dispatcher::start(notifiers, self.outbox.clone(), self.digest.clone());
         tokio::spawn(async move {
-            event!(Level::INFO, "Notifiers thread has been initiated");
-            loop {
-                let execution_result = rx_notifier.recv().unwrap();
-                for notifier in &notifiers {
-                    notifier.notify(&execution_result);
-                }
+            event!(Level::INFO, "Notifiers worker has been initiated");
+            while let Some(notification) = rx_notifier.recv().await {
+                dispatcher.dispatch(notification);
             }
+            event!(
+                Level::ERROR,
+                "Notifier channel has been closed, notifiers stopped"
+            );
         });
 
         event!(Level::INFO, "Initiating the main task loop");
         loop {
-            {
+            let next_execution_at = {
                 let mut task_queue = self.tasks.lock().unwrap();
-                loop {
-                    if task_queue
-                        .peek()
-                        .is_none_or(|t| t.0.next_execution_at() > &Zoned::now().datetime())
-                    {
-                        break;
-                    }
-
+                let now = Zoned::now().datetime();
+                while task_queue
+                    .peek()
+                    .is_some_and(|t| t.0.next_execution_at() <= &now)
+                {
                     let task = task_queue.dequeue();
                     let tx_task = tx_task.clone();
                     let tx_notifier = tx_notifier.clone();
@@ -84,7 +109,23 @@ impl Scheduler {
                         executor::execute_check(task, tx_task, tx_notifier).await;
                     });
                 }
+                task_queue.peek().map(|t| *t.0.next_execution_at())
+            };
+
+            match next_execution_at {
+                Some(next_execution_at) => {
+                    tokio::select! {
+                        _ = time::sleep(Scheduler::time_until(&next_execution_at)) => {}
+                        _ = self.wakeup.notified() => {}
+                    }
+                }
+                None => self.wakeup.notified().await,
             }
         }
     }
+
+    /// The time left until `at`, a moment in the past results in zero.
+    fn time_until(at: &DateTime) -> Duration {
+        Duration::try_from(Zoned::now().datetime().duration_until(*at)).unwrap_or_default()
+    }
 }
@@ -1,8 +1,12 @@
 use jiff::{Zoned, civil::DateTime};
 
 use crate::{
-    checker::{Checker, structs::CheckerResult},
-    task_info::TaskInfo,
+    checker::{
+        Checker,
+        structs::{CheckerResult, CheckerStatus},
+    },
+    notification::{Notification, NotificationKind},
+    task_info::{EscalationTier, RetryPolicy, TaskInfo, TaskState},
     task_logger::TaskLogger,
 };
 
@@ -47,6 +51,125 @@ impl Task {
         &self.checker
     }
 
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.info.retry_policy
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.info.tags
+    }
+
+    pub fn notifiers(&self) -> Option<&Vec<String>> {
+        self.info.notifiers.as_ref()
+    }
+
+    pub fn set_notifiers(&mut self, notifiers: Vec<String>) {
+        self.info.notifiers = Some(notifiers);
+    }
+
+    pub fn escalation(&self) -> &[EscalationTier] {
+        &self.info.escalation
+    }
+
+    /// Run the checker of the task once.
+    pub async fn check(&self) -> CheckerResult {
+        match &self.checker {
+            Checker::Web(checker) => checker.check(&self.info.name).await,
+            Checker::Server(checker) => checker.check(&self.info.name).await,
+        }
+    }
+
+    /// Track the consecutive failures and the state of the task.
+    /// Returns what must be notified when the result changes the state:
+    /// a failure confirmed by the `failure_threshold` while not already down,
+    /// each escalation tier reached while down, or a success while down.
+    /// Repeated failures of a down service are not notified otherwise.
+    pub fn register_result(&mut self, exec_result: &CheckerResult) -> Vec<Notification> {
+        if exec_result.status == CheckerStatus::Success {
+            self.info.consecutive_failures = 0;
+            self.info.failing_since = None;
+            let escalated_tiers = std::mem::take(&mut self.info.escalated_tiers);
+            return match std::mem::replace(&mut self.info.state, TaskState::Up) {
+                TaskState::Down { since } => vec![self.notification(
+                    exec_result,
+                    NotificationKind::Recovered { down_since: since },
+                    self.recovery_notifiers(escalated_tiers),
+                )],
+                _ => vec![],
+            };
+        }
+
+        self.info.consecutive_failures = self.info.consecutive_failures.saturating_add(1);
+        let failing_since = self
+            .info
+            .failing_since
+            .get_or_insert_with(|| exec_result.checked_at.clone())
+            .clone();
+
+        let mut notifications = vec![];
+        let down_since = match &self.info.state {
+            TaskState::Down { since } => since.clone(),
+            _ if self.info.consecutive_failures < self.info.retry_policy.failure_threshold => {
+                return notifications;
+            }
+            _ => {
+                self.info.state = TaskState::Down {
+                    since: failing_since.clone(),
+                };
+                notifications.push(self.notification(
+                    exec_result,
+                    NotificationKind::Down,
+                    self.info.notifiers.clone(),
+                ));
+                failing_since
+            }
+        };
+
+        while let Some(tier) = self.info.escalation.get(self.info.escalated_tiers)
+            && tier.is_reached(
+                &down_since,
+                &exec_result.checked_at,
+                self.info.consecutive_failures,
+            )
+        {
+            self.info.escalated_tiers += 1;
+            notifications.push(self.notification(
+                exec_result,
+                NotificationKind::Escalated {
+                    tier: self.info.escalated_tiers,
+                    down_since: down_since.clone(),
+                },
+                Some(tier.notifiers.clone()),
+            ));
+        }
+
+        notifications
+    }
+
+    fn notification(
+        &self,
+        exec_result: &CheckerResult,
+        kind: NotificationKind,
+        notifiers: Option<Vec<String>>,
+    ) -> Notification {
+        Notification::new(exec_result.clone(), kind, self.info.tags.clone(), notifiers)
+    }
+
+    /// The recovery is sent to the notifiers of the service,
+    /// plus the ones of the escalation tiers notified during the outage.
+    fn recovery_notifiers(&self, escalated_tiers: usize) -> Option<Vec<String>> {
+        let mut notifiers = self.info.notifiers.clone()?;
+        for tier in &self.info.escalation[..escalated_tiers] {
+            for name in &tier.notifiers {
+                if !notifiers.contains(name) {
+                    notifiers.push(name.clone());
+                }
+            }
+        }
+
+        Some(notifiers)
+    }
+
     pub fn log(&mut self, exec_result: &CheckerResult) {
         self.logger.log(exec_result);
     }
@@ -70,3 +193,199 @@ impl PartialEq for Task {
 }
 
 impl Eq for Task {}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use jiff::{SignedDuration, Zoned};
+
+    use super::*;
+    use crate::checker::server::ServerChecker;
+
+    fn task(failure_threshold: u32, escalation: Vec<EscalationTier>) -> Task {
+        let retry_policy = RetryPolicy {
+            failure_threshold,
+            ..RetryPolicy::default()
+        };
+        let info = TaskInfo::new(
+            "test.register_result".to_string(),
+            SignedDuration::from_secs(60),
+            retry_policy,
+            vec![],
+            Some(vec!["mailer".to_string()]),
+            escalation,
+        );
+        let socket = SocketAddr::from(([127, 0, 0, 1], 80));
+        Task::new(
+            info,
+            Checker::Server(ServerChecker::new(socket, Some(Duration::from_secs(1)))),
+        )
+    }
+
+    fn tier(after_failures: u32, notifier: &str) -> EscalationTier {
+        EscalationTier {
+            after: None,
+            after_failures: Some(after_failures),
+            notifiers: vec![notifier.to_string()],
+        }
+    }
+
+    fn result(status: CheckerStatus, checked_at: &Zoned) -> CheckerResult {
+        CheckerResult {
+            checked_at: checked_at.clone(),
+            ..CheckerResult::new("test.register_result".to_string(), status, String::new())
+        }
+    }
+
+    /// Registers a result per status, a minute apart, and returns the notifications of each one.
+    fn register(task: &mut Task, statuses: &[CheckerStatus]) -> Vec<Vec<Notification>> {
+        let start = Zoned::now();
+        statuses
+            .iter()
+            .enumerate()
+            .map(|(minutes, status)| {
+                let checked_at = &start + SignedDuration::from_mins(minutes as i64);
+                task.register_result(&result(*status, &checked_at))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn failures_below_the_threshold_are_not_notified() {
+        let mut task = task(3, vec![]);
+        let notified = register(&mut task, &[CheckerStatus::Error, CheckerStatus::Timeout]);
+        assert!(notified.iter().all(Vec::is_empty));
+        assert_eq!(task.info.state, TaskState::Unknown);
+
+        // A success resets the consecutive failures
+        let notified = register(
+            &mut task,
+            &[
+                CheckerStatus::Success,
+                CheckerStatus::Error,
+                CheckerStatus::Error,
+            ],
+        );
+        assert!(notified.iter().all(Vec::is_empty));
+        assert_eq!(task.info.state, TaskState::Up);
+    }
+
+    #[test]
+    fn confirmed_failure_of_an_unknown_service_is_notified_as_down() {
+        let mut task = task(2, vec![]);
+        let notified = register(&mut task, &[CheckerStatus::Error, CheckerStatus::Error]);
+        assert!(notified[0].is_empty());
+        assert_eq!(notified[1].len(), 1);
+        assert!(matches!(notified[1][0].kind, NotificationKind::Down));
+        assert_eq!(notified[1][0].notifiers, Some(vec!["mailer".to_string()]));
+        // The outage starts at the first failure of the threshold
+        assert_eq!(
+            task.info.state,
+            TaskState::Down {
+                since: &notified[1][0].result.checked_at - SignedDuration::from_mins(1)
+            }
+        );
+    }
+
+    #[test]
+    fn repeated_failures_of_a_down_service_are_not_notified() {
+        let mut task = task(1, vec![]);
+        let notified = register(
+            &mut task,
+            &[
+                CheckerStatus::Error,
+                CheckerStatus::Error,
+                CheckerStatus::Timeout,
+            ],
+        );
+        assert_eq!(notified[0].len(), 1);
+        assert!(notified[1].is_empty());
+        assert!(notified[2].is_empty());
+    }
+
+    #[test]
+    fn recovery_of_a_down_service_carries_the_outage() {
+        let mut task = task(1, vec![]);
+        let notified = register(
+            &mut task,
+            &[
+                CheckerStatus::Error,
+                CheckerStatus::Error,
+                CheckerStatus::Success,
+            ],
+        );
+        assert_eq!(notified[2].len(), 1);
+        assert!(matches!(
+            notified[2][0].kind,
+            NotificationKind::Recovered { .. }
+        ));
+        assert_eq!(notified[2][0].outage(), Some(SignedDuration::from_mins(2)));
+        assert_eq!(task.info.state, TaskState::Up);
+
+        // A success of a service already up is not notified
+        assert!(register(&mut task, &[CheckerStatus::Success])[0].is_empty());
+    }
+
+    #[test]
+    fn escalation_tiers_are_notified_once_each_in_order() {
+        let mut task = task(1, vec![tier(2, "pagerduty"), tier(3, "sms")]);
+        let notified = register(&mut task, &[CheckerStatus::Error; 5]);
+        let routes: Vec<Vec<String>> = notified
+            .iter()
+            .map(|notifications| {
+                notifications
+                    .iter()
+                    .map(|n| {
+                        format!(
+                            "{} {:?}",
+                            n.kind,
+                            n.notifiers.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            routes,
+            vec![
+                vec!["down [\"mailer\"]"],
+                vec!["escalated [\"pagerduty\"]"],
+                vec!["escalated [\"sms\"]"],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn escalation_tiers_reached_together_are_notified_in_order() {
+        let mut task = task(2, vec![tier(2, "pagerduty"), tier(2, "sms")]);
+        let notified = register(&mut task, &[CheckerStatus::Error; 2]);
+        assert!(matches!(notified[1][0].kind, NotificationKind::Down));
+        assert_eq!(notified[1][1].escalation_tier(), Some(1));
+        assert_eq!(notified[1][2].escalation_tier(), Some(2));
+        assert_eq!(notified[1].len(), 3);
+    }
+
+    #[test]
+    fn recovery_is_sent_to_the_escalated_tiers() {
+        let mut task = task(1, vec![tier(2, "pagerduty"), tier(10, "sms")]);
+        let notified = register(
+            &mut task,
+            &[
+                CheckerStatus::Error,
+                CheckerStatus::Error,
+                CheckerStatus::Success,
+            ],
+        );
+        assert_eq!(
+            notified[2][0].notifiers,
+            Some(vec!["mailer".to_string(), "pagerduty".to_string()])
+        );
+
+        // The next outage starts again from the first tier
+        let notified = register(&mut task, &[CheckerStatus::Error, CheckerStatus::Error]);
+        assert_eq!(notified[1][0].escalation_tier(), Some(1));
+    }
+}
@@ -8,15 +8,88 @@ pub struct TaskInfo {
     pub interval: SignedDuration,
     pub last_execution_at: DateTime,
     pub next_execution_at: DateTime,
+    pub retry_policy: RetryPolicy,
+    pub tags: Vec<String>,
+    pub notifiers: Option<Vec<String>>,
+    pub escalation: Vec<EscalationTier>,
+    pub consecutive_failures: u32,
+    pub failing_since: Option<Zoned>,
+    pub state: TaskState,
+    /// How many tiers of the `escalation` were notified since the service is down.
+    pub escalated_tiers: usize,
 }
 
 impl TaskInfo {
-    pub fn new(name: String, interval: SignedDuration) -> Self {
+    pub fn new(
+        name: String,
+        interval: SignedDuration,
+        retry_policy: RetryPolicy,
+        tags: Vec<String>,
+        notifiers: Option<Vec<String>>,
+        escalation: Vec<EscalationTier>,
+    ) -> Self {
         Self {
             name,
             interval,
             last_execution_at: Zoned::now().datetime(),
             next_execution_at: Zoned::now().datetime(),
+            retry_policy,
+            tags,
+            notifiers,
+            escalation,
+            consecutive_failures: 0,
+            failing_since: None,
+            state: TaskState::Unknown,
+            escalated_tiers: 0,
         }
     }
 }
+
+/// Defines how a failure must be confirmed before the service is reported.
+/// `retries` are extra checks made right after a failed one, spaced by `retry_interval`.
+/// `failure_threshold` is the number of consecutive failed executions needed to report it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub retry_interval: SignedDuration,
+    pub failure_threshold: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 0,
+            retry_interval: SignedDuration::from_secs(1),
+            failure_threshold: 1,
+        }
+    }
+}
+
+/// A tier of notifiers alerted when a service is still down after `after` time
+/// or after `after_failures` consecutive failures, whichever comes first.
+/// The tiers are notified in order, each one only once per outage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationTier {
+    pub after: Option<SignedDuration>,
+    pub after_failures: Option<u32>,
+    pub notifiers: Vec<String>,
+}
+
+impl EscalationTier {
+    /// Whether the tier must be notified for a service down since `down_since`,
+    /// given the failed check made at `checked_at`.
+    pub fn is_reached(&self, down_since: &Zoned, checked_at: &Zoned, failures: u32) -> bool {
+        self.after
+            .is_some_and(|after| checked_at.duration_since(down_since) >= after)
+            || self.after_failures.is_some_and(|after| failures >= after)
+    }
+}
+
+/// The known state of the service checked by a task.
+/// A task starts as `Unknown` until its first success or confirmed failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    Unknown,
+    Up,
+    Down { since: Zoned },
+}